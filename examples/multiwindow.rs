@@ -11,7 +11,13 @@ struct State2 {
 
 impl MainLoop for State1 {
     fn handle_event(&mut self, fb: &mut Framebuffer, event: &Event) {
-        if matches!(event, Event::KeyPress(Key::Escape)) {
+        if matches!(
+            event,
+            Event::KeyPress {
+                key: Key::Escape,
+                ..
+            }
+        ) {
             fb.close();
         }
     }
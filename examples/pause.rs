@@ -4,7 +4,13 @@ struct State;
 
 impl MainLoop for State {
     fn handle_event(&mut self, fb: &mut Framebuffer, event: &Event) {
-        if matches!(event, Event::KeyPress(Key::Escape)) {
+        if matches!(
+            event,
+            Event::KeyPress {
+                key: Key::Escape,
+                ..
+            }
+        ) {
             fb.close();
         }
     }
@@ -16,37 +22,17 @@ impl MainLoop for State {
 
         d.pause(Key::C);
 
-        draw_circle(d, 70, 150, 30, 0x00cc00);
+        d.fill_circle(70, 150, 30, 0x00cc00);
         d.pause(Key::C);
 
-        draw_circle(d, 150, 150, 30, 0x00cc00);
+        d.fill_circle(150, 150, 30, 0x00cc00);
         d.pause(Key::C);
 
-        draw_circle(d, 230, 150, 30, 0x00cc00);
+        d.fill_circle(230, 150, 30, 0x00cc00);
         d.pause(Key::C);
     }
 }
 
-fn draw_circle(d: &mut DrawHandle, x: u32, y: u32, radius: u32, color: u32) {
-    let x_min = x.saturating_sub(radius + 1);
-    let y_min = y.saturating_sub(radius + 1);
-
-    let x_max = x.saturating_add(radius).min(d.width() - 1);
-    let y_max = y.saturating_add(radius).min(d.height() - 1);
-
-    for dx in x_min..x_max {
-        for dy in y_min..y_max {
-            let x_off = (dx as i32) - (x as i32) + 1;
-            let y_off = (dy as i32) - (y as i32) + 1;
-            let rad_sq = (radius * radius) as i32;
-
-            if x_off * x_off + y_off * y_off <= rad_sq {
-                d.set(dx, dy, color);
-            }
-        }
-    }
-}
-
 fn main() {
     let mut state = State;
     let mut fb = Framebuffer::new(300, 300, "C to continue", 60);
@@ -1,4 +1,4 @@
-use picofb::{DrawHandle, Event, Framebuffer, Key, MainLoop};
+use picofb::{DrawHandle, Event, Framebuffer, Key, MainLoop, MouseButton, Scancode, WindowOptions};
 
 #[derive(Default)]
 struct MyGameState {
@@ -11,20 +11,38 @@ struct MyGameState {
 impl MainLoop for MyGameState {
     fn handle_event(&mut self, fb: &mut Framebuffer, event: &Event) {
         match event {
-            Event::KeyPress(key) => match key {
-                Key::W => self.input_forward = 1,
-                Key::S => self.input_forward = -1,
-                Key::D => self.input_right = 1,
-                Key::A => self.input_right = -1,
-                Key::Escape => fb.close(),
+            Event::KeyPress {
+                key: Key::Escape, ..
+            } => fb.close(),
+            Event::KeyPress {
+                key: Key::Return,
+                modifiers,
+                repeat: false,
+                ..
+            } if modifiers.alt() => fb.toggle_fullscreen(),
+            // Scancodes keep WASD in place on AZERTY and other layouts.
+            Event::KeyPress { scancode, .. } => match scancode {
+                Scancode::W => self.input_forward = 1,
+                Scancode::S => self.input_forward = -1,
+                Scancode::D => self.input_right = 1,
+                Scancode::A => self.input_right = -1,
                 _ => (),
             },
-            Event::KeyRelease(key) => match key {
-                Key::W | Key::S => self.input_forward = 0,
-                Key::D | Key::A => self.input_right = 0,
+            Event::KeyRelease { scancode, .. } => match scancode {
+                Scancode::W | Scancode::S => self.input_forward = 0,
+                Scancode::D | Scancode::A => self.input_right = 0,
                 _ => (),
             },
-            Event::MouseMove(_, _) => (),
+            Event::MouseDown {
+                button: MouseButton::Left,
+                x,
+                y,
+                ..
+            } => {
+                self.pos_x = *x as f32;
+                self.pos_y = *y as f32;
+            }
+            _ => (),
         }
     }
 
@@ -39,25 +57,18 @@ impl MainLoop for MyGameState {
     fn render(&mut self, d: &mut DrawHandle) {
         d.clear();
 
-        let size = 10;
-
-        let px = self.pos_x as u32;
-        let py = self.pos_y as u32;
-
-        let xmax = (px + size).min(d.width());
-        let ymax = (py + size).min(d.height());
-
-        for y in py..ymax {
-            for x in px..xmax {
-                d.set(x, y, 0xff0000);
-            }
-        }
+        d.fill_rect(self.pos_x as i32, self.pos_y as i32, 10, 10, 0xff0000);
     }
 }
 
 fn main() {
     let mut state = MyGameState::default();
-    let mut fb = Framebuffer::new(300, 300, "example", 60);
+    let options = WindowOptions {
+        resizable: true,
+        ..WindowOptions::default()
+    };
+    let mut fb = Framebuffer::with_options(300, 300, "example", 60, options);
+    fb.set_screenshot_key(Some(Key::F12));
 
     fb.run(&mut state);
 }
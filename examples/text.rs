@@ -6,7 +6,13 @@ struct State {
 
 impl MainLoop for State {
     fn handle_event(&mut self, fb: &mut Framebuffer, event: &Event) {
-        if matches!(event, Event::KeyPress(Key::Escape)) {
+        if matches!(
+            event,
+            Event::KeyPress {
+                key: Key::Escape,
+                ..
+            }
+        ) {
             fb.close();
         }
     }
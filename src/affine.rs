@@ -0,0 +1,178 @@
+use crate::{DrawHandle, Image};
+
+/// A 2D affine transformation mapping `(x, y)` to
+/// `(m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine {
+    pub m: [[f32; 3]; 2],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sampling {
+    #[default]
+    Nearest,
+    Bilinear,
+}
+
+impl Affine {
+    pub const IDENTITY: Self = Self {
+        m: [[1., 0., 0.], [0., 1., 0.]],
+    };
+
+    pub fn translate(x: f32, y: f32) -> Self {
+        Self {
+            m: [[1., 0., x], [0., 1., y]],
+        }
+    }
+
+    pub fn scale(sx: f32, sy: f32) -> Self {
+        Self {
+            m: [[sx, 0., 0.], [0., sy, 0.]],
+        }
+    }
+
+    /// Rotation by `radians`, clockwise on screen since y points down.
+    pub fn rotate(radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+
+        Self {
+            m: [[cos, -sin, 0.], [sin, cos, 0.]],
+        }
+    }
+
+    pub fn shear(kx: f32, ky: f32) -> Self {
+        Self {
+            m: [[1., kx, 0.], [ky, 1., 0.]],
+        }
+    }
+
+    /// Returns the transformation that applies `self` first and `next` second.
+    #[must_use]
+    pub fn then(self, next: Self) -> Self {
+        let [[a, b, c], [d, e, f]] = next.m;
+        let [[g, h, i], [j, k, l]] = self.m;
+
+        Self {
+            m: [
+                [a * g + b * j, a * h + b * k, a * i + b * l + c],
+                [d * g + e * j, d * h + e * k, d * i + e * l + f],
+            ],
+        }
+    }
+
+    pub fn inverse(self) -> Option<Self> {
+        let [[a, b, c], [d, e, f]] = self.m;
+        let det = a * e - b * d;
+
+        if det == 0. || !det.is_finite() {
+            return Option::None;
+        }
+
+        let inv = 1. / det;
+
+        Some(Self {
+            m: [
+                [e * inv, -b * inv, (b * f - e * c) * inv],
+                [-d * inv, a * inv, (d * c - a * f) * inv],
+            ],
+        })
+    }
+
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        let [[a, b, c], [d, e, f]] = self.m;
+
+        (a * x + b * y + c, d * x + e * y + f)
+    }
+}
+
+impl Default for Affine {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl DrawHandle<'_> {
+    /// Draws `image` transformed by `transform`, which maps image pixel coordinates to framebuffer
+    /// coordinates. Every framebuffer pixel whose center maps inside the image is sampled and
+    /// written with the current blend mode.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn blit_transformed(&mut self, image: &Image, transform: Affine, sampling: Sampling) {
+        let Some(inverse) = transform.inverse() else {
+            return;
+        };
+
+        let (img_w, img_h) = (image.width() as f32, image.height() as f32);
+        let corners = [(0., 0.), (img_w, 0.), (0., img_h), (img_w, img_h)]
+            .map(|(x, y)| transform.apply(x, y));
+
+        let (mut min_x, mut min_y) = (f32::INFINITY, f32::INFINITY);
+        let (mut max_x, mut max_y) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+        for (x, y) in corners {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+
+        let x0 = min_x.floor().max(0.) as u32;
+        let y0 = min_y.floor().max(0.) as u32;
+        let x1 = max_x.ceil().min(self.widthf()) as u32;
+        let y1 = max_y.ceil().min(self.heightf()) as u32;
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let (sx, sy) = inverse.apply(x as f32 + 0.5, y as f32 + 0.5);
+
+                if sx < 0. || sy < 0. || sx >= img_w || sy >= img_h {
+                    continue;
+                }
+
+                let color = match sampling {
+                    Sampling::Nearest => {
+                        image.pixels()[(sy as u32 * image.width() + sx as u32) as usize]
+                    }
+                    Sampling::Bilinear => sample_bilinear(image, sx, sy),
+                };
+
+                self.blend_index((y * self.width() + x) as usize, color);
+            }
+        }
+    }
+}
+
+/// Interpolates the four pixels around `(x, y)`, clamping at the image edges.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn sample_bilinear(image: &Image, x: f32, y: f32) -> u32 {
+    let (x, y) = (x - 0.5, y - 0.5);
+    let (fx, fy) = (x - x.floor(), y - y.floor());
+
+    let max_x = image.width() as i64 - 1;
+    let max_y = image.height() as i64 - 1;
+    let x0 = (x.floor() as i64).clamp(0, max_x) as u32;
+    let y0 = (y.floor() as i64).clamp(0, max_y) as u32;
+    let x1 = (x.floor() as i64 + 1).clamp(0, max_x) as u32;
+    let y1 = (y.floor() as i64 + 1).clamp(0, max_y) as u32;
+
+    let px = |x: u32, y: u32| image.pixels()[(y * image.width() + x) as usize].to_be_bytes();
+    let (p00, p10, p01, p11) = (px(x0, y0), px(x1, y0), px(x0, y1), px(x1, y1));
+
+    let mut out = [0; 4];
+
+    for (i, channel) in out.iter_mut().enumerate() {
+        let top = f32::from(p00[i]) * (1. - fx) + f32::from(p10[i]) * fx;
+        let bottom = f32::from(p01[i]) * (1. - fx) + f32::from(p11[i]) * fx;
+
+        *channel = (top * (1. - fy) + bottom * fy).round() as u8;
+    }
+
+    u32::from_be_bytes(out)
+}
@@ -0,0 +1,112 @@
+/// How a color written to the framebuffer is combined with the pixel already there. Except for
+/// `Opaque`, the source is weighted by its alpha byte, so `0x80_ff_00_00` is half-transparent red.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Overwrite the destination, ignoring the source alpha.
+    #[default]
+    Opaque,
+    /// Source-over compositing.
+    Alpha,
+    Add,
+    Multiply,
+    Screen,
+}
+
+impl BlendMode {
+    pub fn apply(self, src: u32, dst: u32) -> u32 {
+        let [sa, sr, sg, sb] = src.to_be_bytes();
+        let [da, dr, dg, db] = dst.to_be_bytes();
+
+        if self == Self::Opaque || (self == Self::Alpha && sa == 0xff) {
+            return src | 0xff_00_00_00;
+        }
+
+        let channel = |s: u8, d: u8| -> u8 {
+            let (s, d, a) = (u32::from(s), u32::from(d), u32::from(sa));
+
+            let out = match self {
+                Self::Opaque => s,
+                Self::Alpha => mul(s, a) + mul(d, 255 - a),
+                Self::Add => (d + mul(s, a)).min(255),
+                Self::Multiply => mul(d, mul(s, a) + 255 - a),
+                Self::Screen => 255 - mul(255 - d, 255 - mul(s, a)),
+            };
+
+            out as u8
+        };
+
+        let alpha = u32::from(sa) + mul(u32::from(da), 255 - u32::from(sa));
+
+        u32::from_be_bytes([
+            alpha as u8,
+            channel(sr, dr),
+            channel(sg, dg),
+            channel(sb, db),
+        ])
+    }
+}
+
+/// Multiplies two values in `0..=255` as if they were fractions of 255, rounding to nearest.
+fn mul(a: u32, b: u32) -> u32 {
+    let x = a * b + 128;
+
+    (x + (x >> 8)) >> 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: u32 = 0xff_ff_00_00;
+    const BLUE: u32 = 0xff_00_00_ff;
+
+    #[test]
+    fn mul_rounds_to_nearest() {
+        for a in 0..=255 {
+            for b in 0..=255 {
+                assert_eq!(mul(a, b), (a * b + 127) / 255, "{a} * {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn opaque_ignores_alpha() {
+        assert_eq!(BlendMode::Opaque.apply(0x00_12_34_56, BLUE), 0xff_12_34_56);
+    }
+
+    #[test]
+    fn alpha_is_source_over() {
+        let mode = BlendMode::Alpha;
+
+        assert_eq!(mode.apply(RED, BLUE), RED);
+        assert_eq!(mode.apply(0x00_ff_00_00, BLUE), BLUE);
+        assert_eq!(mode.apply(0x80_ff_00_00, BLUE), 0xff_80_00_7f);
+        assert_eq!(mode.apply(0x80_ff_00_00, 0x00_00_00_00), 0x80_80_00_00);
+    }
+
+    #[test]
+    fn add_saturates() {
+        let mode = BlendMode::Add;
+
+        assert_eq!(mode.apply(0xff_80_80_80, 0xff_c0_10_00), 0xff_ff_90_80);
+        assert_eq!(mode.apply(0x80_ff_ff_ff, 0xff_10_10_10), 0xff_90_90_90);
+        assert_eq!(mode.apply(0x00_ff_ff_ff, 0xff_10_10_10), 0xff_10_10_10);
+    }
+
+    #[test]
+    fn multiply_darkens() {
+        let mode = BlendMode::Multiply;
+
+        assert_eq!(mode.apply(0xff_80_ff_00, 0xff_ff_80_80), 0xff_80_80_00);
+        assert_eq!(mode.apply(0x00_00_00_00, 0xff_ff_80_80), 0xff_ff_80_80);
+        assert_eq!(mode.apply(0x80_00_00_00, 0xff_ff_80_80), 0xff_7f_40_40);
+    }
+
+    #[test]
+    fn screen_lightens() {
+        let mode = BlendMode::Screen;
+
+        assert_eq!(mode.apply(0xff_80_ff_00, 0xff_80_00_40), 0xff_c0_ff_40);
+        assert_eq!(mode.apply(0x00_ff_ff_ff, 0xff_80_00_40), 0xff_80_00_40);
+    }
+}
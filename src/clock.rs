@@ -0,0 +1,133 @@
+use sdl2_sys::{SDL_Delay, SDL_GetPerformanceCounter, SDL_GetPerformanceFrequency};
+
+use crate::Framebuffer;
+
+// `SDL_Delay` can oversleep by a scheduler tick, so spin for the last few milliseconds.
+const SPIN_MS: f64 = 2.;
+
+/// Time as seen by the main loop, in seconds since the framebuffer was created. Window
+/// framebuffers read SDL's high-resolution performance counter. Headless ones use a simulated
+/// clock that advances by exactly one update step per rendered frame, so their runs are
+/// deterministic.
+#[derive(Debug)]
+pub struct Clock {
+    source: Source,
+    frame_start: f64,
+    frame_delta: f64,
+}
+
+/// Timing of the frame being rendered, from [`crate::DrawHandle::frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FrameInfo {
+    /// How far the frame lies between the last two update steps, from just after the previous
+    /// one at 0 to exactly at the latest one at 1. Drawing `previous + (latest - previous) *
+    /// alpha` keeps motion smooth when the frame rate doesn't match the update rate.
+    pub alpha: f32,
+    /// Real seconds since the previous frame started, see [`Clock::frame_delta`].
+    pub delta: f64,
+    /// The time the frame is rendered at, on the same time base as [`Clock::now`].
+    pub time: f64,
+}
+
+#[derive(Debug)]
+enum Source {
+    Real { start: u64, frequency: u64 },
+    Simulated { time: f64 },
+}
+
+impl Clock {
+    pub(crate) fn real() -> Self {
+        let source = Source::Real {
+            start: unsafe { SDL_GetPerformanceCounter() },
+            frequency: unsafe { SDL_GetPerformanceFrequency() },
+        };
+
+        Self::from_source(source)
+    }
+
+    pub(crate) fn simulated() -> Self {
+        Self::from_source(Source::Simulated { time: 0. })
+    }
+
+    fn from_source(source: Source) -> Self {
+        Self {
+            source,
+            frame_start: 0.,
+            frame_delta: 0.,
+        }
+    }
+
+    /// Seconds since the clock started. A simulated clock only moves between frames.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn now(&self) -> f64 {
+        match self.source {
+            Source::Real { start, frequency } => {
+                let ticks = unsafe { SDL_GetPerformanceCounter() } - start;
+
+                ticks as f64 / frequency as f64
+            }
+            Source::Simulated { time } => time,
+        }
+    }
+
+    /// The time at which the current frame started.
+    pub fn frame_start(&self) -> f64 {
+        self.frame_start
+    }
+
+    /// Seconds since the current frame started.
+    pub fn elapsed(&self) -> f64 {
+        self.now() - self.frame_start
+    }
+
+    /// Seconds between the starts of the previous and the current frame, or since the clock
+    /// started during the first frame.
+    pub fn frame_delta(&self) -> f64 {
+        self.frame_delta
+    }
+
+    pub fn is_simulated(&self) -> bool {
+        matches!(self.source, Source::Simulated { .. })
+    }
+
+    pub(crate) fn start_frame(&mut self, step: f64) -> f64 {
+        if let Source::Simulated { time } = &mut self.source {
+            *time += step;
+        }
+
+        let now = self.now();
+        self.frame_delta = now - self.frame_start;
+        self.frame_start = now;
+
+        now
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn wait_until(&self, deadline: f64) {
+        if self.is_simulated() {
+            return;
+        }
+
+        loop {
+            let remaining_ms = (deadline - self.now()) * 1000.;
+
+            if remaining_ms <= 0. {
+                break;
+            }
+
+            if remaining_ms > SPIN_MS {
+                unsafe { SDL_Delay((remaining_ms - SPIN_MS) as u32) };
+            } else {
+                std::hint::spin_loop();
+            }
+        }
+    }
+}
+
+impl Framebuffer {
+    /// The clock driving [`Framebuffer::run`], with the same time base as the `time`
+    /// passed to [`crate::MainLoop::update`].
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+}
@@ -0,0 +1,342 @@
+use super::has_alpha;
+use crate::{Error, Image};
+
+const FILE_HEADER_SIZE: u32 = 14;
+const INFO_HEADER_SIZE: u32 = 40;
+const V4_HEADER_SIZE: u32 = 108;
+
+const BI_RGB: u32 = 0;
+const BI_BITFIELDS: u32 = 3;
+const BI_ALPHABITFIELDS: u32 = 6;
+
+/// Writes a bottom-up 24-bit BMP, or a 32-bit one with an alpha mask if any pixel isn't opaque.
+pub fn encode(width: u32, height: u32, pixels: &[u32]) -> Vec<u8> {
+    let with_alpha = has_alpha(pixels);
+    let (bpp, header_size) = if with_alpha {
+        (32, V4_HEADER_SIZE)
+    } else {
+        (24, INFO_HEADER_SIZE)
+    };
+
+    let stride = row_stride(width, bpp).expect("image too large for BMP");
+    let data_offset = FILE_HEADER_SIZE + header_size;
+    let data_size = stride * height as usize;
+
+    let mut out = Vec::with_capacity(data_offset as usize + data_size);
+
+    out.extend_from_slice(b"BM");
+    put_u32(&mut out, data_offset + data_size as u32);
+    put_u32(&mut out, 0);
+    put_u32(&mut out, data_offset);
+
+    put_u32(&mut out, header_size);
+    put_u32(&mut out, width);
+    put_u32(&mut out, height);
+    put_u16(&mut out, 1);
+    put_u16(&mut out, bpp);
+    put_u32(&mut out, if with_alpha { BI_BITFIELDS } else { BI_RGB });
+    put_u32(&mut out, data_size as u32);
+    put_u32(&mut out, 2835); // 72 DPI
+    put_u32(&mut out, 2835);
+    put_u32(&mut out, 0);
+    put_u32(&mut out, 0);
+
+    if with_alpha {
+        for mask in [0x00_ff_00_00, 0x00_00_ff_00, 0x00_00_00_ff, 0xff_00_00_00] {
+            put_u32(&mut out, mask);
+        }
+
+        out.extend_from_slice(b"BGRs");
+        out.resize(data_offset as usize, 0);
+    }
+
+    for row in pixels.chunks_exact(width as usize).rev() {
+        let row_start = out.len();
+
+        for &pixel in row {
+            let [a, r, g, b] = pixel.to_be_bytes();
+
+            out.extend_from_slice(&[b, g, r]);
+
+            if with_alpha {
+                out.push(a);
+            }
+        }
+
+        out.resize(row_start + stride, 0);
+    }
+
+    out
+}
+
+/// Reads uncompressed 1, 2, 4, 8, 24 and 32-bit BMP files, bottom-up or top-down.
+pub fn decode(bytes: &[u8]) -> Result<Image, Error> {
+    let r = Reader(bytes);
+
+    if bytes.get(..2) != Some(b"BM") {
+        return Err(invalid("not a BMP file"));
+    }
+
+    let data_offset = r.u32(10)? as usize;
+    let header_size = r.u32(14)?;
+
+    if header_size < INFO_HEADER_SIZE {
+        return Err(invalid("OS/2 bitmaps are not supported"));
+    }
+
+    let width = r.i32(18)?;
+    let raw_height = r.i32(22)?;
+    let bpp = r.u16(28)?;
+    let compression = r.u32(30)?;
+    let colors_used = r.u32(46)?;
+
+    let Ok(width) = u32::try_from(width) else {
+        return Err(invalid("negative width"));
+    };
+    let height = raw_height.unsigned_abs();
+    let top_down = raw_height < 0;
+
+    if !matches!(bpp, 1 | 2 | 4 | 8 | 24 | 32) {
+        return Err(invalid("unsupported bit depth"));
+    }
+
+    let masks = match compression {
+        BI_RGB => Option::None,
+        BI_BITFIELDS | BI_ALPHABITFIELDS if bpp == 32 => {
+            // Either part of a V2+ header or directly following the info header, same offset.
+            let at = (FILE_HEADER_SIZE + INFO_HEADER_SIZE) as usize;
+            let alpha = if header_size >= 56 || compression == BI_ALPHABITFIELDS {
+                r.u32(at + 12)?
+            } else {
+                0
+            };
+
+            Some([r.u32(at)?, r.u32(at + 4)?, r.u32(at + 8)?, alpha])
+        }
+        _ => return Err(invalid("compressed bitmaps are not supported")),
+    };
+
+    let palette = if bpp <= 8 {
+        let count = if colors_used == 0 {
+            1 << bpp
+        } else {
+            colors_used as usize
+        };
+        let start = FILE_HEADER_SIZE
+            .checked_add(header_size)
+            .ok_or_else(|| invalid("header too large"))? as usize;
+        let table = count
+            .checked_mul(4)
+            .and_then(|len| bytes.get(start..start.checked_add(len)?))
+            .ok_or_else(|| invalid("truncated palette"))?;
+
+        table
+            .chunks_exact(4)
+            .map(|bgr| u32::from_be_bytes([0xff, bgr[2], bgr[1], bgr[0]]))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let stride = row_stride(width, bpp).ok_or_else(|| invalid("image too large"))?;
+    let data_size = stride
+        .checked_mul(height as usize)
+        .ok_or_else(|| invalid("image too large"))?;
+    let data = bytes
+        .get(data_offset..)
+        .filter(|data| data.len() >= data_size)
+        .ok_or_else(|| invalid("truncated pixel data"))?;
+
+    let mut pixels = Vec::with_capacity(width as usize * height as usize);
+
+    for y in 0..height as usize {
+        let src_row = if top_down { y } else { height as usize - 1 - y };
+        let row = &data[src_row * stride..(src_row + 1) * stride];
+
+        for x in 0..width as usize {
+            let pixel = match bpp {
+                1 | 2 | 4 | 8 => {
+                    let bits = usize::from(bpp);
+                    let byte = row[x * bits / 8];
+                    let shift = 8 - bits - (x * bits % 8);
+                    let idx = (byte >> shift) as usize & ((1 << bits) - 1);
+
+                    *palette
+                        .get(idx)
+                        .ok_or_else(|| invalid("palette index out of range"))?
+                }
+                24 => u32::from_be_bytes([0xff, row[x * 3 + 2], row[x * 3 + 1], row[x * 3]]),
+                32 => {
+                    let raw = u32::from_le_bytes([
+                        row[x * 4],
+                        row[x * 4 + 1],
+                        row[x * 4 + 2],
+                        row[x * 4 + 3],
+                    ]);
+
+                    match masks {
+                        Some(masks) => unpack_masked(raw, masks),
+                        None => raw,
+                    }
+                }
+                _ => unreachable!("bit depth checked above"),
+            };
+
+            pixels.push(pixel);
+        }
+    }
+
+    // Plain 32-bit bitmaps usually leave the fourth byte zeroed rather than storing alpha.
+    if bpp == 32 && masks.is_none_or(|m| m[3] == 0) {
+        pixels.iter_mut().for_each(|pixel| *pixel |= 0xff_00_00_00);
+    }
+
+    Ok(Image::from_pixels(width, height, pixels))
+}
+
+fn unpack_masked(raw: u32, masks: [u32; 4]) -> u32 {
+    let channel = |mask: u32| -> u32 {
+        if mask == 0 {
+            return 0;
+        }
+
+        let value = u64::from((raw & mask) >> mask.trailing_zeros());
+        let max = u64::from(mask >> mask.trailing_zeros());
+
+        #[allow(clippy::cast_possible_truncation)]
+        let scaled = ((value * 255 + max / 2) / max) as u32;
+
+        scaled
+    };
+
+    let [r, g, b, a] = masks.map(channel);
+
+    a << 24 | r << 16 | g << 8 | b
+}
+
+fn row_stride(width: u32, bpp: u16) -> Option<usize> {
+    let bits = (width as usize).checked_mul(usize::from(bpp))?;
+
+    bits.div_ceil(32).checked_mul(4)
+}
+
+fn put_u16(out: &mut Vec<u8>, v: u16) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, v: u32) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+struct Reader<'b>(&'b [u8]);
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&self, offset: usize) -> Result<[u8; N], Error> {
+        self.0
+            .get(offset..offset + N)
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| invalid("truncated header"))
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16, Error> {
+        self.bytes(offset).map(u16::from_le_bytes)
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, Error> {
+        self.bytes(offset).map(u32::from_le_bytes)
+    }
+
+    fn i32(&self, offset: usize) -> Result<i32, Error> {
+        self.bytes(offset).map(i32::from_le_bytes)
+    }
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::Decode(msg.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(with_alpha: bool) -> Vec<u8> {
+        let alpha = if with_alpha { 0x80 } else { 0xff };
+        let pixels = [0x00_ff_00_00, 0x00_00_ff_00, 0x00_00_00_ff, 0x00_12_34_56]
+            .map(|rgb| alpha << 24 | rgb);
+
+        encode(2, 2, &pixels)
+    }
+
+    fn patch(bytes: &mut [u8], offset: usize, value: &[u8]) {
+        bytes[offset..offset + value.len()].copy_from_slice(value);
+    }
+
+    #[test]
+    fn truncated_files_are_errors() {
+        for with_alpha in [false, true] {
+            let bytes = sample(with_alpha);
+
+            assert!(decode(&bytes).is_ok());
+
+            for len in 0..bytes.len() {
+                assert!(decode(&bytes[..len]).is_err(), "{len} bytes");
+            }
+        }
+    }
+
+    #[test]
+    fn oversized_header_is_an_error() {
+        let mut bytes = sample(false);
+        patch(&mut bytes, 14, &u32::MAX.to_le_bytes());
+        patch(&mut bytes, 28, &8u16.to_le_bytes());
+
+        assert!(matches!(decode(&bytes), Err(Error::Decode(_))));
+    }
+
+    #[test]
+    fn oversized_dimensions_are_errors() {
+        let mut bytes = sample(false);
+        patch(&mut bytes, 18, &i32::MAX.to_le_bytes());
+        patch(&mut bytes, 22, &i32::MIN.to_le_bytes());
+
+        for bpp in [1, 24, 32, 4000] {
+            patch(&mut bytes, 28, &u16::to_le_bytes(bpp));
+            assert!(matches!(decode(&bytes), Err(Error::Decode(_))), "{bpp} bpp");
+        }
+    }
+
+    #[test]
+    fn full_width_masks_are_scaled() {
+        let mut bytes = sample(true);
+        let masks = (FILE_HEADER_SIZE + INFO_HEADER_SIZE) as usize;
+        patch(&mut bytes, masks, &u32::MAX.to_le_bytes());
+
+        // Red now spans the whole pixel, so it scales `0x80xxxxxx` down to `0x80`.
+        let image = decode(&bytes).unwrap();
+        assert_eq!(
+            image.pixels(),
+            [0x80_80_00_00, 0x80_80_ff_00, 0x80_80_00_ff, 0x80_80_34_56]
+        );
+    }
+
+    #[test]
+    fn palette_images_are_decoded() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"BM");
+        put_u32(&mut bytes, 62 + 4);
+        put_u32(&mut bytes, 0);
+        put_u32(&mut bytes, 62);
+        put_u32(&mut bytes, INFO_HEADER_SIZE);
+        put_u32(&mut bytes, 2);
+        put_u32(&mut bytes, 1);
+        put_u16(&mut bytes, 1);
+        put_u16(&mut bytes, 1);
+        put_u32(&mut bytes, BI_RGB);
+        bytes.extend_from_slice(&[0; 20]);
+        bytes.extend_from_slice(&[0x00, 0x00, 0xff, 0x00, 0xff, 0x00, 0x00, 0x00]);
+        bytes.extend_from_slice(&[0b0100_0000, 0, 0, 0]);
+
+        let image = decode(&bytes).unwrap();
+        assert_eq!(image.pixels(), [0xff_ff_00_00, 0xff_00_00_ff]);
+    }
+}
@@ -0,0 +1,241 @@
+//! Image file formats. PPM is always available, the others are behind cargo features of the same
+//! name: `bmp` (enabled by default), `png` and `qoi`.
+
+#[cfg(feature = "bmp")]
+mod bmp;
+#[cfg(feature = "png")]
+mod png;
+mod ppm;
+#[cfg(feature = "qoi")]
+mod qoi;
+
+use std::fs;
+use std::path::Path;
+
+use crate::{Error, Image};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Bmp,
+    Png,
+    Qoi,
+}
+
+impl ImageFormat {
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_ascii_lowercase().as_str() {
+            "ppm" => Some(Self::Ppm),
+            "bmp" => Some(Self::Bmp),
+            "png" => Some(Self::Png),
+            "qoi" => Some(Self::Qoi),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// Guesses the format from the first bytes of a file.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [b'P', b'6', ..] => Some(Self::Ppm),
+            [b'B', b'M', ..] => Some(Self::Bmp),
+            [0x89, b'P', b'N', b'G', ..] => Some(Self::Png),
+            [b'q', b'o', b'i', b'f', ..] => Some(Self::Qoi),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ppm => "ppm",
+            Self::Bmp => "bmp",
+            Self::Png => "png",
+            Self::Qoi => "qoi",
+        }
+    }
+
+    /// The most widely supported format that was compiled in, preferring PNG, then BMP.
+    pub fn preferred() -> Self {
+        [Self::Png, Self::Bmp]
+            .into_iter()
+            .find(|format| format.is_enabled())
+            .unwrap_or(Self::Ppm)
+    }
+
+    /// Whether support for this format was compiled in.
+    pub fn is_enabled(self) -> bool {
+        match self {
+            Self::Ppm => true,
+            Self::Bmp => cfg!(feature = "bmp"),
+            Self::Png => cfg!(feature = "png"),
+            Self::Qoi => cfg!(feature = "qoi"),
+        }
+    }
+
+    pub(crate) fn unsupported(self) -> Error {
+        Error::UnsupportedFormat(format!(
+            "{} (enable the \"{}\" feature)",
+            self.extension(),
+            self.extension()
+        ))
+    }
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Image, Error> {
+    let format = ImageFormat::detect(bytes)
+        .ok_or_else(|| Error::UnsupportedFormat("unrecognized file signature".to_owned()))?;
+
+    match format {
+        ImageFormat::Ppm => ppm::decode(bytes),
+        #[cfg(feature = "bmp")]
+        ImageFormat::Bmp => bmp::decode(bytes),
+        #[cfg(feature = "png")]
+        ImageFormat::Png => png::decode(bytes),
+        #[cfg(feature = "qoi")]
+        ImageFormat::Qoi => qoi::decode(bytes),
+        #[allow(unreachable_patterns)]
+        _ => Err(format.unsupported()),
+    }
+}
+
+/// Encodes row-major ARGB8888 pixels. Formats that support transparency only store the alpha
+/// channel when some pixel isn't fully opaque.
+pub fn encode(
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+    pixels: &[u32],
+) -> Result<Vec<u8>, Error> {
+    assert_eq!(pixels.len(), width as usize * height as usize);
+
+    match format {
+        ImageFormat::Ppm => Ok(ppm::encode(width, height, pixels)),
+        #[cfg(feature = "bmp")]
+        ImageFormat::Bmp => Ok(bmp::encode(width, height, pixels)),
+        #[cfg(feature = "png")]
+        ImageFormat::Png => png::encode(width, height, pixels),
+        #[cfg(feature = "qoi")]
+        ImageFormat::Qoi => qoi::encode(width, height, pixels),
+        #[allow(unreachable_patterns)]
+        _ => Err(format.unsupported()),
+    }
+}
+
+#[cfg(any(feature = "bmp", feature = "png", feature = "qoi"))]
+fn has_alpha(pixels: &[u32]) -> bool {
+    pixels.iter().any(|&pixel| pixel >> 24 != 0xff)
+}
+
+/// Converts ARGB pixels to RGBA bytes, or RGB bytes without alpha.
+#[cfg(any(feature = "png", feature = "qoi"))]
+fn to_rgb_bytes(pixels: &[u32], with_alpha: bool) -> Vec<u8> {
+    if with_alpha {
+        pixels
+            .iter()
+            .flat_map(|&pixel| pixel.rotate_left(8).to_be_bytes())
+            .collect()
+    } else {
+        pixels
+            .iter()
+            .flat_map(|&pixel| {
+                let [_, r, g, b] = pixel.to_be_bytes();
+                [r, g, b]
+            })
+            .collect()
+    }
+}
+
+pub fn load(path: impl AsRef<Path>) -> Result<Image, Error> {
+    decode(&fs::read(path)?)
+}
+
+/// Saves pixels in the format matching the extension of `path`.
+pub fn save(path: impl AsRef<Path>, width: u32, height: u32, pixels: &[u32]) -> Result<(), Error> {
+    let path = path.as_ref();
+    let format = ImageFormat::from_path(path)
+        .ok_or_else(|| Error::UnsupportedFormat(path.display().to_string()))?;
+
+    fs::write(path, encode(format, width, height, pixels)?)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMATS: [ImageFormat; 4] = [
+        ImageFormat::Ppm,
+        ImageFormat::Bmp,
+        ImageFormat::Png,
+        ImageFormat::Qoi,
+    ];
+
+    fn gradient(alpha: impl Fn(u8) -> u8) -> Vec<u32> {
+        (0..7 * 5)
+            .map(|i| u32::from_be_bytes([alpha(i), i * 7, 255 - i * 3, i.wrapping_mul(11)]))
+            .collect()
+    }
+
+    fn round_trip(format: ImageFormat, pixels: &[u32]) -> Image {
+        let bytes = encode(format, 7, 5, pixels).unwrap();
+
+        assert_eq!(ImageFormat::detect(&bytes), Some(format));
+
+        let image = decode(&bytes).unwrap();
+        assert_eq!((image.width(), image.height()), (7, 5));
+
+        image
+    }
+
+    #[test]
+    fn opaque_round_trip() {
+        let pixels = gradient(|_| 0xff);
+
+        for format in FORMATS.into_iter().filter(|f| f.is_enabled()) {
+            assert_eq!(round_trip(format, &pixels).pixels(), pixels, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn alpha_round_trip() {
+        let pixels = gradient(|i| i * 7);
+
+        for format in FORMATS.into_iter().filter(|f| f.is_enabled()) {
+            let expected: Vec<u32> = if format == ImageFormat::Ppm {
+                pixels.iter().map(|p| p | 0xff_00_00_00).collect()
+            } else {
+                pixels.clone()
+            };
+
+            assert_eq!(round_trip(format, &pixels).pixels(), expected, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn disabled_formats_are_reported() {
+        for format in FORMATS.into_iter().filter(|f| !f.is_enabled()) {
+            assert!(matches!(
+                encode(format, 1, 1, &[0]),
+                Err(Error::UnsupportedFormat(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn garbage_is_rejected() {
+        assert!(decode(b"").is_err());
+        assert!(decode(b"GIF89a").is_err());
+
+        for format in FORMATS.into_iter().filter(|f| f.is_enabled()) {
+            let bytes = encode(format, 7, 5, &gradient(|_| 0xff)).unwrap();
+
+            assert!(decode(&bytes[..bytes.len() / 2]).is_err(), "{format:?}");
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use png::{BitDepth, ColorType, Decoder, Encoder, Transformations};
+
+use super::{has_alpha, to_rgb_bytes};
+use crate::{Error, Image};
+
+pub fn encode(width: u32, height: u32, pixels: &[u32]) -> Result<Vec<u8>, Error> {
+    let with_alpha = has_alpha(pixels);
+    let mut out = Vec::new();
+    let mut encoder = Encoder::new(&mut out, width, height);
+
+    encoder.set_depth(BitDepth::Eight);
+
+    encoder.set_color(if with_alpha {
+        ColorType::Rgba
+    } else {
+        ColorType::Rgb
+    });
+
+    let data = to_rgb_bytes(pixels, with_alpha);
+
+    let mut writer = encoder.write_header().map_err(encode_err)?;
+    writer.write_image_data(&data).map_err(encode_err)?;
+    writer.finish().map_err(encode_err)?;
+
+    Ok(out)
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Image, Error> {
+    let mut decoder = Decoder::new(bytes);
+    decoder.set_transformations(Transformations::normalize_to_color8());
+
+    let mut reader = decoder.read_info().map_err(decode_err)?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(decode_err)?;
+
+    let channels = info.color_type.samples();
+    let pixels = buf[..info.buffer_size()]
+        .chunks_exact(channels)
+        .map(|px| match *px {
+            [l] => u32::from_be_bytes([0xff, l, l, l]),
+            [l, a] => u32::from_be_bytes([a, l, l, l]),
+            [r, g, b] => u32::from_be_bytes([0xff, r, g, b]),
+            [r, g, b, a] => u32::from_be_bytes([a, r, g, b]),
+            _ => unreachable!("PNG pixels have 1 to 4 samples"),
+        })
+        .collect();
+
+    Ok(Image::from_pixels(info.width, info.height, pixels))
+}
+
+fn encode_err(err: png::EncodingError) -> Error {
+    Error::Encode(err.to_string())
+}
+
+fn decode_err(err: png::DecodingError) -> Error {
+    Error::Decode(err.to_string())
+}
@@ -0,0 +1,82 @@
+use crate::{Error, Image};
+
+pub fn encode(width: u32, height: u32, pixels: &[u32]) -> Vec<u8> {
+    let header = format!("P6\n{width} {height}\n255\n");
+    let mut out = Vec::with_capacity(header.len() + pixels.len() * 3);
+
+    out.extend_from_slice(header.as_bytes());
+
+    for &pixel in pixels {
+        let [_, r, g, b] = pixel.to_be_bytes();
+        out.extend_from_slice(&[r, g, b]);
+    }
+
+    out
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Image, Error> {
+    let mut ptr = bytes;
+
+    let magic = next_token(&mut ptr)?;
+    if magic != b"P6" {
+        return Err(invalid("not a binary PPM file"));
+    }
+
+    let width = parse_u32(next_token(&mut ptr)?)?;
+    let height = parse_u32(next_token(&mut ptr)?)?;
+    let maxval = parse_u32(next_token(&mut ptr)?)?;
+
+    if maxval != 255 {
+        return Err(invalid("only 8-bit PPM files are supported"));
+    }
+
+    // Exactly one whitespace byte separates the header from the raster.
+    let raster = ptr.get(1..).unwrap_or_default();
+    let num_pixels = width as usize * height as usize;
+
+    if raster.len() / 3 < num_pixels {
+        return Err(invalid("unexpected end of file"));
+    }
+
+    let pixels = raster
+        .chunks_exact(3)
+        .take(num_pixels)
+        .map(|rgb| u32::from_be_bytes([0xff, rgb[0], rgb[1], rgb[2]]))
+        .collect();
+
+    Ok(Image::from_pixels(width, height, pixels))
+}
+
+fn next_token<'b>(ptr: &mut &'b [u8]) -> Result<&'b [u8], Error> {
+    loop {
+        match ptr.first() {
+            Some(b'#') => {
+                let line_end = ptr.iter().position(|&b| b == b'\n').unwrap_or(ptr.len());
+                *ptr = &ptr[line_end..];
+            }
+            Some(b) if b.is_ascii_whitespace() => *ptr = &ptr[1..],
+            Some(_) => break,
+            None => return Err(invalid("unexpected end of header")),
+        }
+    }
+
+    let len = ptr
+        .iter()
+        .position(u8::is_ascii_whitespace)
+        .unwrap_or(ptr.len());
+    let (token, rest) = ptr.split_at(len);
+    *ptr = rest;
+
+    Ok(token)
+}
+
+fn parse_u32(token: &[u8]) -> Result<u32, Error> {
+    std::str::from_utf8(token)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid("malformed PPM header"))
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::Decode(msg.to_owned())
+}
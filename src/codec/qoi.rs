@@ -0,0 +1,28 @@
+use qoi::Channels;
+
+use super::{has_alpha, to_rgb_bytes};
+use crate::{Error, Image};
+
+pub fn encode(width: u32, height: u32, pixels: &[u32]) -> Result<Vec<u8>, Error> {
+    // The channel count is inferred from the buffer length.
+    let data = to_rgb_bytes(pixels, has_alpha(pixels));
+
+    qoi::encode_to_vec(data, width, height).map_err(|err| Error::Encode(err.to_string()))
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Image, Error> {
+    let (header, data) = qoi::decode_to_vec(bytes).map_err(|err| Error::Decode(err.to_string()))?;
+
+    let pixels = match header.channels {
+        Channels::Rgb => data
+            .chunks_exact(3)
+            .map(|px| u32::from_be_bytes([0xff, px[0], px[1], px[2]]))
+            .collect(),
+        Channels::Rgba => data
+            .chunks_exact(4)
+            .map(|px| u32::from_be_bytes([px[3], px[0], px[1], px[2]]))
+            .collect(),
+    };
+
+    Ok(Image::from_pixels(header.width, header.height, pixels))
+}
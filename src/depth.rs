@@ -0,0 +1,102 @@
+use crate::raster::rasterize_triangle;
+use crate::DrawHandle;
+
+impl DrawHandle<'_> {
+    /// Returns the window's depth buffer, allocating it on first use. It has one entry per pixel
+    /// and is reset to infinity by [`DrawHandle::clear`] and [`DrawHandle::clear_depth`].
+    pub fn depth_buffer(&mut self) -> &mut [f32] {
+        let num_pixels = self.pixels.len();
+
+        if self.depth.len() != num_pixels {
+            self.depth.clear();
+            self.depth.resize(num_pixels, f32::INFINITY);
+        }
+
+        self.depth
+    }
+
+    pub fn clear_depth(&mut self) {
+        self.depth_buffer().fill(f32::INFINITY);
+    }
+
+    /// Sets the pixel if `z` is closer than the stored depth, and records `z` as the new depth.
+    /// Returns whether the pixel was written.
+    pub fn set_depth(&mut self, x: u32, y: u32, z: f32, color: u32) -> bool {
+        if x >= self.width() || y >= self.height() {
+            return false;
+        }
+
+        let idx = (y * self.width() + x) as usize;
+        let depth = &mut self.depth_buffer()[idx];
+
+        if z >= *depth {
+            return false;
+        }
+
+        *depth = z;
+        self.set(x, y, color);
+
+        true
+    }
+
+    /// Depth-tested variant of [`DrawHandle::fill_triangle`]. The depth of each pixel is
+    /// interpolated linearly in screen space from the vertices' `z`.
+    pub fn fill_triangle_depth(
+        &mut self,
+        p0: (f32, f32, f32),
+        p1: (f32, f32, f32),
+        p2: (f32, f32, f32),
+        color: u32,
+    ) {
+        self.fill_triangle_depth_with(p0, p1, p2, |_, _, _| color);
+    }
+
+    /// Depth-tested variant of [`DrawHandle::fill_triangle_with`]. The shader is only called for
+    /// pixels that pass the depth test.
+    pub fn fill_triangle_depth_with(
+        &mut self,
+        p0: (f32, f32, f32),
+        p1: (f32, f32, f32),
+        p2: (f32, f32, f32),
+        mut shader: impl FnMut(u32, u32, [f32; 3]) -> u32,
+    ) {
+        let (width, height) = (self.width(), self.height());
+        let vertices = [(p0.0, p0.1), (p1.0, p1.1), (p2.0, p2.1)];
+
+        self.depth_buffer();
+
+        rasterize_triangle(width, height, vertices, |x, y, bary| {
+            let z = bary[0] * p0.2 + bary[1] * p1.2 + bary[2] * p2.2;
+            let idx = (y * width + x) as usize;
+
+            if z >= self.depth[idx] {
+                return;
+            }
+
+            self.depth[idx] = z;
+
+            let color = shader(x, y, bary);
+            self.set(x, y, color);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Window;
+
+    #[test]
+    fn resize_resets_depth() {
+        let mut window = Window::headless(2, 4);
+        window
+            .start_render()
+            .unwrap()
+            .set_depth(1, 0, 0.5, 0xff_ff_ff_ff);
+
+        window.resize_buffer(4, 2).unwrap();
+
+        let mut d = window.start_render().unwrap();
+        assert_eq!(d.depth_buffer().len(), 8);
+        assert!(d.depth_buffer().iter().all(|&z| z == f32::INFINITY));
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::io;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Init(String),
+    Window(String),
+    Renderer(String),
+    Texture(String),
+    Lock(String),
+    DisplayMode(String),
+    VSync(String),
+    Io(IoError),
+    Decode(String),
+    Encode(String),
+    UnsupportedFormat(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (action, msg) = match self {
+            Self::Init(msg) => ("initialize SDL", msg),
+            Self::Window(msg) => ("create window", msg),
+            Self::Renderer(msg) => ("create renderer", msg),
+            Self::Texture(msg) => ("create texture", msg),
+            Self::Lock(msg) => ("lock texture", msg),
+            Self::DisplayMode(msg) => ("change display mode", msg),
+            Self::VSync(msg) => ("set vsync", msg),
+            Self::Io(err) => return write!(f, "I/O error: {err}"),
+            Self::Decode(msg) => ("decode image", msg),
+            Self::Encode(msg) => ("encode image", msg),
+            Self::UnsupportedFormat(name) => return write!(f, "Unsupported image format: {name}"),
+        };
+
+        write!(f, "Failed to {action}: {msg}")
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(&*err.0),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(IoError(Arc::new(err)))
+    }
+}
+
+/// An [`io::Error`] that can be cloned and compared, by kind and message.
+#[derive(Debug, Clone)]
+pub struct IoError(Arc<io::Error>);
+
+impl IoError {
+    pub fn kind(&self) -> io::ErrorKind {
+        self.0.kind()
+    }
+
+    pub fn get_ref(&self) -> &io::Error {
+        &self.0
+    }
+}
+
+impl PartialEq for IoError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind() == other.kind() && self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl Eq for IoError {}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
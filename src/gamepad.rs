@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::ffi::CStr;
+
+#[allow(clippy::wildcard_imports)]
+use sdl2_sys::*;
+
+use crate::{event_type, Event, Framebuffer};
+
+/// Identifies a connected gamepad for as long as it stays connected. A gamepad that is unplugged
+/// and plugged back in gets a new id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GamepadId(i32);
+
+/// Buttons named after their position on an Xbox controller, so `A` is the bottom face button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    A,
+    B,
+    X,
+    Y,
+    Back,
+    Guide,
+    Start,
+    LeftStick,
+    RightStick,
+    LeftShoulder,
+    RightShoulder,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+    /// Share, capture or microphone button, depending on the controller.
+    Misc1,
+    Paddle1,
+    Paddle2,
+    Paddle3,
+    Paddle4,
+    Touchpad,
+}
+
+/// Stick axes range from -1 to 1, positive to the right and down. Triggers range from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    TriggerLeft,
+    TriggerRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadStick {
+    Left,
+    Right,
+}
+
+pub(crate) struct Gamepads {
+    pads: HashMap<GamepadId, Gamepad>,
+    deadzone: f32,
+}
+
+struct Gamepad {
+    handle: *mut SDL_GameController,
+    axes: [f32; 6],
+}
+
+const BUTTONS: [GamepadButton; 21] = [
+    GamepadButton::A,
+    GamepadButton::B,
+    GamepadButton::X,
+    GamepadButton::Y,
+    GamepadButton::Back,
+    GamepadButton::Guide,
+    GamepadButton::Start,
+    GamepadButton::LeftStick,
+    GamepadButton::RightStick,
+    GamepadButton::LeftShoulder,
+    GamepadButton::RightShoulder,
+    GamepadButton::DpadUp,
+    GamepadButton::DpadDown,
+    GamepadButton::DpadLeft,
+    GamepadButton::DpadRight,
+    GamepadButton::Misc1,
+    GamepadButton::Paddle1,
+    GamepadButton::Paddle2,
+    GamepadButton::Paddle3,
+    GamepadButton::Paddle4,
+    GamepadButton::Touchpad,
+];
+
+const AXES: [GamepadAxis; 6] = [
+    GamepadAxis::LeftX,
+    GamepadAxis::LeftY,
+    GamepadAxis::RightX,
+    GamepadAxis::RightY,
+    GamepadAxis::TriggerLeft,
+    GamepadAxis::TriggerRight,
+];
+
+impl GamepadButton {
+    /// Converts an `SDL_GameControllerButton` value, or returns `None` for buttons added in SDL
+    /// versions newer than this crate knows about.
+    pub fn from_raw(raw: u8) -> Option<Self> {
+        BUTTONS.get(usize::from(raw)).copied()
+    }
+
+    fn to_sdl(self) -> SDL_GameControllerButton {
+        use SDL_GameControllerButton as B;
+
+        match self {
+            Self::A => B::SDL_CONTROLLER_BUTTON_A,
+            Self::B => B::SDL_CONTROLLER_BUTTON_B,
+            Self::X => B::SDL_CONTROLLER_BUTTON_X,
+            Self::Y => B::SDL_CONTROLLER_BUTTON_Y,
+            Self::Back => B::SDL_CONTROLLER_BUTTON_BACK,
+            Self::Guide => B::SDL_CONTROLLER_BUTTON_GUIDE,
+            Self::Start => B::SDL_CONTROLLER_BUTTON_START,
+            Self::LeftStick => B::SDL_CONTROLLER_BUTTON_LEFTSTICK,
+            Self::RightStick => B::SDL_CONTROLLER_BUTTON_RIGHTSTICK,
+            Self::LeftShoulder => B::SDL_CONTROLLER_BUTTON_LEFTSHOULDER,
+            Self::RightShoulder => B::SDL_CONTROLLER_BUTTON_RIGHTSHOULDER,
+            Self::DpadUp => B::SDL_CONTROLLER_BUTTON_DPAD_UP,
+            Self::DpadDown => B::SDL_CONTROLLER_BUTTON_DPAD_DOWN,
+            Self::DpadLeft => B::SDL_CONTROLLER_BUTTON_DPAD_LEFT,
+            Self::DpadRight => B::SDL_CONTROLLER_BUTTON_DPAD_RIGHT,
+            Self::Misc1 => B::SDL_CONTROLLER_BUTTON_MISC1,
+            Self::Paddle1 => B::SDL_CONTROLLER_BUTTON_PADDLE1,
+            Self::Paddle2 => B::SDL_CONTROLLER_BUTTON_PADDLE2,
+            Self::Paddle3 => B::SDL_CONTROLLER_BUTTON_PADDLE3,
+            Self::Paddle4 => B::SDL_CONTROLLER_BUTTON_PADDLE4,
+            Self::Touchpad => B::SDL_CONTROLLER_BUTTON_TOUCHPAD,
+        }
+    }
+}
+
+impl GamepadAxis {
+    pub fn from_raw(raw: u8) -> Option<Self> {
+        AXES.get(usize::from(raw)).copied()
+    }
+
+    fn is_trigger(self) -> bool {
+        matches!(self, Self::TriggerLeft | Self::TriggerRight)
+    }
+}
+
+impl Gamepads {
+    pub(crate) fn new() -> Self {
+        Self {
+            pads: HashMap::new(),
+            deadzone: 0.15,
+        }
+    }
+
+    /// Updates the connected gamepads from a controller event, returning the event to deliver.
+    pub(crate) fn handle_event(&mut self, event: &SDL_Event) -> Option<Event> {
+        unsafe {
+            match event.type_ {
+                event_type::CONTROLLERDEVICEADDED => self.open(event.cdevice.which),
+                event_type::CONTROLLERDEVICEREMOVED => {
+                    let id = GamepadId(event.cdevice.which);
+                    let pad = self.pads.remove(&id)?;
+
+                    SDL_GameControllerClose(pad.handle);
+
+                    Some(Event::GamepadDisconnected(id))
+                }
+                event_type::CONTROLLERBUTTONDOWN | event_type::CONTROLLERBUTTONUP => {
+                    let id = GamepadId(event.cbutton.which);
+                    let button = GamepadButton::from_raw(event.cbutton.button)?;
+
+                    if !self.pads.contains_key(&id) {
+                        return Option::None;
+                    }
+
+                    if event.type_ == event_type::CONTROLLERBUTTONDOWN {
+                        Some(Event::GamepadButtonDown { id, button })
+                    } else {
+                        Some(Event::GamepadButtonUp { id, button })
+                    }
+                }
+                event_type::CONTROLLERAXISMOTION => {
+                    let id = GamepadId(event.caxis.which);
+                    let axis = GamepadAxis::from_raw(event.caxis.axis)?;
+                    let value = apply_deadzone(normalize(event.caxis.value), self.deadzone);
+                    let last = &mut self.pads.get_mut(&id)?.axes[axis as usize];
+
+                    // Jitter inside the deadzone would otherwise flood the event queue with zeros.
+                    if *last == value {
+                        return Option::None;
+                    }
+
+                    *last = value;
+
+                    Some(Event::GamepadAxisMotion { id, axis, value })
+                }
+                _ => Option::None,
+            }
+        }
+    }
+
+    fn open(&mut self, device_index: i32) -> Option<Event> {
+        let handle = unsafe { SDL_GameControllerOpen(device_index) };
+
+        if handle.is_null() {
+            return Option::None;
+        }
+
+        let id =
+            GamepadId(unsafe { SDL_JoystickInstanceID(SDL_GameControllerGetJoystick(handle)) });
+
+        // SDL reports controllers that were connected before startup again after opening them.
+        if self.pads.contains_key(&id) {
+            unsafe { SDL_GameControllerClose(handle) };
+            return Option::None;
+        }
+
+        self.pads.insert(
+            id,
+            Gamepad {
+                handle,
+                axes: [0.; 6],
+            },
+        );
+
+        Some(Event::GamepadConnected(id))
+    }
+
+    fn get(&self, id: GamepadId) -> Option<*mut SDL_GameController> {
+        self.pads.get(&id).map(|pad| pad.handle)
+    }
+
+    /// Closes every gamepad. Has to happen before `SDL_Quit`, so it isn't left to `Drop`.
+    pub(crate) fn close_all(&mut self) {
+        for (_, pad) in self.pads.drain() {
+            unsafe { SDL_GameControllerClose(pad.handle) };
+        }
+    }
+}
+
+impl Framebuffer {
+    /// Connected gamepads, in no particular order. Joysticks that SDL has no gamepad mapping for
+    /// are not included.
+    pub fn gamepads(&self) -> Vec<GamepadId> {
+        self.gamepads.pads.keys().copied().collect()
+    }
+
+    pub fn gamepad_name(&self, id: GamepadId) -> Option<String> {
+        let name = unsafe { SDL_GameControllerName(self.gamepads.get(id)?) };
+
+        if name.is_null() {
+            return Option::None;
+        }
+
+        Some(
+            unsafe { CStr::from_ptr(name) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+
+    pub fn gamepad_button_pressed(&self, id: GamepadId, button: GamepadButton) -> bool {
+        let Some(handle) = self.gamepads.get(id) else {
+            return false;
+        };
+
+        unsafe { SDL_GameControllerGetButton(handle, button.to_sdl()) != 0 }
+    }
+
+    /// The position of `axis` with the deadzone applied, or 0 if the gamepad is disconnected.
+    pub fn gamepad_axis(&self, id: GamepadId, axis: GamepadAxis) -> f32 {
+        let raw = self.raw_axis(id, axis);
+
+        apply_deadzone(raw, self.gamepads.deadzone)
+    }
+
+    /// Both axes of a stick. The deadzone is applied to the distance from the center, so diagonal
+    /// movement isn't snapped to the axes.
+    pub fn gamepad_stick(&self, id: GamepadId, stick: GamepadStick) -> (f32, f32) {
+        let (x_axis, y_axis) = match stick {
+            GamepadStick::Left => (GamepadAxis::LeftX, GamepadAxis::LeftY),
+            GamepadStick::Right => (GamepadAxis::RightX, GamepadAxis::RightY),
+        };
+        let (x, y) = (self.raw_axis(id, x_axis), self.raw_axis(id, y_axis));
+        let len = x.hypot(y);
+
+        if len <= self.gamepads.deadzone {
+            return (0., 0.);
+        }
+
+        let scale = apply_deadzone(len.min(1.), self.gamepads.deadzone) / len;
+
+        (x * scale, y * scale)
+    }
+
+    /// Sets the fraction of each axis' range around the center that reads as 0. Values outside it
+    /// are rescaled to still cover the full range. Defaults to 0.15.
+    pub fn set_gamepad_deadzone(&mut self, deadzone: f32) {
+        self.gamepads.deadzone = deadzone.clamp(0., 0.99);
+    }
+
+    /// Starts rumbling with the given motor strengths from 0 to 1 for `duration_ms`, replacing any
+    /// previous rumble. Returns false if the gamepad is disconnected or can't rumble.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn gamepad_rumble(&mut self, id: GamepadId, low: f32, high: f32, duration_ms: u32) -> bool {
+        let Some(handle) = self.gamepads.get(id) else {
+            return false;
+        };
+
+        let strength = |value: f32| (value.clamp(0., 1.) * f32::from(u16::MAX)).round() as u16;
+
+        unsafe { SDL_GameControllerRumble(handle, strength(low), strength(high), duration_ms) == 0 }
+    }
+
+    fn raw_axis(&self, id: GamepadId, axis: GamepadAxis) -> f32 {
+        let Some(handle) = self.gamepads.get(id) else {
+            return 0.;
+        };
+
+        let sdl_axis = match axis {
+            GamepadAxis::LeftX => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_LEFTX,
+            GamepadAxis::LeftY => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_LEFTY,
+            GamepadAxis::RightX => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_RIGHTX,
+            GamepadAxis::RightY => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_RIGHTY,
+            GamepadAxis::TriggerLeft => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_TRIGGERLEFT,
+            GamepadAxis::TriggerRight => SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_TRIGGERRIGHT,
+        };
+        let value = normalize(unsafe { SDL_GameControllerGetAxis(handle, sdl_axis) });
+
+        if axis.is_trigger() {
+            value.max(0.)
+        } else {
+            value
+        }
+    }
+}
+
+/// Maps SDL's `-32768..=32767` to `-1..=1`.
+fn normalize(value: i16) -> f32 {
+    (f32::from(value) / 32767.).max(-1.)
+}
+
+fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    if value.abs() <= deadzone {
+        return 0.;
+    }
+
+    value.signum() * (value.abs() - deadzone) / (1. - deadzone)
+}
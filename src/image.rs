@@ -0,0 +1,297 @@
+use std::path::Path;
+
+use crate::{codec, DrawHandle, Error, ImageFormat};
+
+/// An owned ARGB8888 bitmap, in the same pixel format as the framebuffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u32>,
+}
+
+/// Clockwise rotation applied when blitting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BlitOptions {
+    /// Mirror horizontally. Flips are applied before rotation.
+    pub flip_x: bool,
+    /// Mirror vertically.
+    pub flip_y: bool,
+    pub rotation: Rotation,
+    /// Source pixels with this RGB value are skipped. The alpha byte is ignored.
+    pub color_key: Option<u32>,
+}
+
+impl Image {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize],
+        }
+    }
+
+    /// Wraps row-major pixels. Panics if `pixels` doesn't hold exactly `width * height` entries.
+    pub fn from_pixels(width: u32, height: u32, pixels: Vec<u32>) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width as usize * height as usize,
+            "pixel count doesn't match {width}x{height}"
+        );
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [u32] {
+        &mut self.pixels
+    }
+
+    pub fn into_pixels(self) -> Vec<u32> {
+        self.pixels
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> Option<u32> {
+        if x >= self.width || y >= self.height {
+            return Option::None;
+        }
+
+        Some(self.pixels[self.index(x, y)])
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, color: u32) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+
+        let idx = self.index(x, y);
+        self.pixels[idx] = color;
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// Loads an image, detecting the format from the file contents.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        codec::load(path)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        codec::decode(bytes)
+    }
+
+    /// Saves the image in the format matching the extension of `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        codec::save(path, self.width, self.height, &self.pixels)
+    }
+
+    pub fn encode(&self, format: ImageFormat) -> Result<Vec<u8>, Error> {
+        codec::encode(format, self.width, self.height, &self.pixels)
+    }
+}
+
+impl DrawHandle<'_> {
+    /// Copies the pixels drawn so far into an image. The alpha byte, which the window ignores, is
+    /// set to `ff`.
+    pub fn snapshot(&self) -> Image {
+        let pixels = self.pixels.iter().map(|p| p | 0xff_00_00_00).collect();
+
+        Image::from_pixels(self.width(), self.height(), pixels)
+    }
+
+    /// Draws the whole image with its top-left corner at `(x, y)`. Pixels are written with the
+    /// current blend mode, so use [`crate::BlendMode::Alpha`] for translucent sprites.
+    pub fn blit(&mut self, image: &Image, x: i32, y: i32, options: BlitOptions) {
+        self.blit_region(image, (0, 0, image.width, image.height), x, y, options);
+    }
+
+    /// Draws the `(x, y, width, height)` part of the image given by `src` with its top-left corner
+    /// at `(x, y)`. `src` is clipped to the image.
+    pub fn blit_region(
+        &mut self,
+        image: &Image,
+        src: (u32, u32, u32, u32),
+        x: i32,
+        y: i32,
+        options: BlitOptions,
+    ) {
+        let (src_x, src_y) = (src.0.min(image.width), src.1.min(image.height));
+        let src_w = src.2.min(image.width - src_x);
+        let src_h = src.3.min(image.height - src_y);
+
+        if src_w == 0 || src_h == 0 {
+            return;
+        }
+
+        let (dst_w, dst_h) = match options.rotation {
+            Rotation::Deg0 | Rotation::Deg180 => (src_w, src_h),
+            Rotation::Deg90 | Rotation::Deg270 => (src_h, src_w),
+        };
+
+        let Some((u0, u1)) = clip_axis(x, dst_w, self.width()) else {
+            return;
+        };
+        let Some((v0, v1)) = clip_axis(y, dst_h, self.height()) else {
+            return;
+        };
+
+        let key = options.color_key.map(|key| key & 0x00_ff_ff_ff);
+
+        for v in v0..v1 {
+            for u in u0..u1 {
+                let (mut sx, mut sy) = match options.rotation {
+                    Rotation::Deg0 => (u, v),
+                    Rotation::Deg90 => (v, src_h - 1 - u),
+                    Rotation::Deg180 => (src_w - 1 - u, src_h - 1 - v),
+                    Rotation::Deg270 => (src_w - 1 - v, u),
+                };
+
+                if options.flip_x {
+                    sx = src_w - 1 - sx;
+                }
+
+                if options.flip_y {
+                    sy = src_h - 1 - sy;
+                }
+
+                let color = image.pixels[image.index(src_x + sx, src_y + sy)];
+
+                if key == Some(color & 0x00_ff_ff_ff) {
+                    continue;
+                }
+
+                let dst_x = x.wrapping_add_unsigned(u) as u32;
+                let dst_y = y.wrapping_add_unsigned(v) as u32;
+
+                self.blend_index(
+                    dst_y as usize * self.width() as usize + dst_x as usize,
+                    color,
+                );
+            }
+        }
+    }
+}
+
+/// The offsets into a `size` long span at `pos` that land inside `0..len`.
+fn clip_axis(pos: i32, size: u32, len: u32) -> Option<(u32, u32)> {
+    let start = (-i64::from(pos)).clamp(0, i64::from(size));
+    let end = (i64::from(len) - i64::from(pos)).clamp(0, i64::from(size));
+
+    (start < end).then_some((start as u32, end as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Window;
+
+    const BG: u32 = 0xff_00_00_00;
+
+    // a b c
+    // d e f
+    fn sample() -> Image {
+        Image::from_pixels(3, 2, (1..=6).map(|i| 0xff_00_00_00 | i).collect())
+    }
+
+    fn blit(width: u32, height: u32, x: i32, y: i32, options: BlitOptions) -> Vec<u32> {
+        let mut window = Window::headless(width, height);
+        let mut d = window.start_render().unwrap();
+        d.as_slice().fill(BG);
+        d.blit(&sample(), x, y, options);
+
+        window.pixels().unwrap().iter().map(|&p| p & 0xff).collect()
+    }
+
+    fn rotated(rotation: Rotation, flip_x: bool, flip_y: bool) -> Vec<u32> {
+        let options = BlitOptions {
+            flip_x,
+            flip_y,
+            rotation,
+            color_key: None,
+        };
+        let (w, h) = match rotation {
+            Rotation::Deg0 | Rotation::Deg180 => (3, 2),
+            Rotation::Deg90 | Rotation::Deg270 => (2, 3),
+        };
+
+        blit(w, h, 0, 0, options)
+    }
+
+    #[test]
+    fn rotations_are_clockwise() {
+        assert_eq!(rotated(Rotation::Deg0, false, false), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(rotated(Rotation::Deg90, false, false), [4, 1, 5, 2, 6, 3]);
+        assert_eq!(rotated(Rotation::Deg180, false, false), [6, 5, 4, 3, 2, 1]);
+        assert_eq!(rotated(Rotation::Deg270, false, false), [3, 6, 2, 5, 1, 4]);
+    }
+
+    #[test]
+    fn flips_apply_before_rotation() {
+        assert_eq!(rotated(Rotation::Deg0, true, false), [3, 2, 1, 6, 5, 4]);
+        assert_eq!(rotated(Rotation::Deg0, false, true), [4, 5, 6, 1, 2, 3]);
+        assert_eq!(rotated(Rotation::Deg90, true, false), [6, 3, 5, 2, 4, 1]);
+        assert_eq!(rotated(Rotation::Deg90, false, true), [1, 4, 2, 5, 3, 6]);
+        assert_eq!(rotated(Rotation::Deg270, true, true), [4, 1, 5, 2, 6, 3]);
+    }
+
+    #[test]
+    fn color_key_ignores_alpha() {
+        let options = BlitOptions {
+            color_key: Some(0x12_00_00_05),
+            ..BlitOptions::default()
+        };
+
+        assert_eq!(blit(3, 2, 0, 0, options), [1, 2, 3, 4, 0, 6]);
+    }
+
+    #[test]
+    fn blits_are_clipped() {
+        let options = BlitOptions::default();
+
+        assert_eq!(blit(3, 2, -1, 1, options), [0, 0, 0, 2, 3, 0]);
+        assert_eq!(blit(3, 2, 2, -1, options), [0, 0, 4, 0, 0, 0]);
+        assert_eq!(blit(3, 2, 3, 0, options), [0; 6]);
+    }
+
+    #[test]
+    fn region_is_clipped_to_the_image() {
+        let mut window = Window::headless(3, 2);
+        let mut d = window.start_render().unwrap();
+        d.blit_region(&sample(), (1, 1, 5, 5), 0, 0, BlitOptions::default());
+
+        let pixels = window.pixels().unwrap();
+        assert_eq!(pixels[..2], [0xff_00_00_05, 0xff_00_00_06]);
+        assert_eq!(pixels[2..], [0; 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "pixel count")]
+    fn huge_dimensions_dont_overflow() {
+        Image::from_pixels(70_000, 70_000, Vec::new());
+    }
+}
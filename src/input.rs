@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Framebuffer, Key, MouseButton, Scancode};
+
+/// Keyboard and mouse button state as seen by the current update step. Edges are kept for exactly
+/// one step, so a key pressed and released between two steps reports both.
+#[derive(Debug, Default)]
+pub struct Input {
+    keys: Buttons<Key>,
+    scancodes: Buttons<Scancode>,
+    mouse: Buttons<MouseButton>,
+}
+
+#[derive(Debug)]
+struct Buttons<T> {
+    states: HashMap<T, ButtonState>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ButtonState {
+    down: bool,
+    just_pressed: bool,
+    just_released: bool,
+    held: f32,
+}
+
+impl Input {
+    pub fn key_pressed(&self, key: Key) -> bool {
+        self.keys.pressed(key)
+    }
+
+    /// Whether `key` went down since the previous update step. Key repeats don't count.
+    pub fn key_just_pressed(&self, key: Key) -> bool {
+        self.keys.just_pressed(key)
+    }
+
+    pub fn key_just_released(&self, key: Key) -> bool {
+        self.keys.just_released(key)
+    }
+
+    /// Seconds of update time `key` has been held for, 0 on the step it was pressed and while it
+    /// is up.
+    pub fn key_held_for(&self, key: Key) -> f32 {
+        self.keys.held_for(key)
+    }
+
+    pub fn scancode_pressed(&self, scancode: Scancode) -> bool {
+        self.scancodes.pressed(scancode)
+    }
+
+    pub fn scancode_just_pressed(&self, scancode: Scancode) -> bool {
+        self.scancodes.just_pressed(scancode)
+    }
+
+    pub fn scancode_just_released(&self, scancode: Scancode) -> bool {
+        self.scancodes.just_released(scancode)
+    }
+
+    pub fn scancode_held_for(&self, scancode: Scancode) -> f32 {
+        self.scancodes.held_for(scancode)
+    }
+
+    pub fn mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.mouse.pressed(button)
+    }
+
+    pub fn mouse_button_just_pressed(&self, button: MouseButton) -> bool {
+        self.mouse.just_pressed(button)
+    }
+
+    pub fn mouse_button_just_released(&self, button: MouseButton) -> bool {
+        self.mouse.just_released(button)
+    }
+
+    pub fn mouse_button_held_for(&self, button: MouseButton) -> f32 {
+        self.mouse.held_for(button)
+    }
+
+    pub(crate) fn set_key(&mut self, key: Key, scancode: Scancode, down: bool) {
+        self.keys.set(key, down);
+        self.scancodes.set(scancode, down);
+    }
+
+    pub(crate) fn set_mouse_button(&mut self, button: MouseButton, down: bool) {
+        self.mouse.set(button, down);
+    }
+
+    /// Clears the edges and advances the held durations after an update step.
+    pub(crate) fn end_step(&mut self, dt: f32) {
+        self.keys.end_step(dt);
+        self.scancodes.end_step(dt);
+        self.mouse.end_step(dt);
+    }
+}
+
+impl<T: Copy + Eq + Hash> Buttons<T> {
+    fn pressed(&self, button: T) -> bool {
+        self.get(button).down
+    }
+
+    fn just_pressed(&self, button: T) -> bool {
+        self.get(button).just_pressed
+    }
+
+    fn just_released(&self, button: T) -> bool {
+        self.get(button).just_released
+    }
+
+    fn held_for(&self, button: T) -> f32 {
+        self.get(button).held
+    }
+
+    fn get(&self, button: T) -> ButtonState {
+        self.states.get(&button).copied().unwrap_or_default()
+    }
+
+    fn set(&mut self, button: T, down: bool) {
+        let state = self.states.entry(button).or_default();
+
+        if down && !state.down {
+            state.just_pressed = true;
+            state.held = 0.;
+        } else if !down && state.down {
+            state.just_released = true;
+            state.held = 0.;
+        }
+
+        state.down = down;
+    }
+
+    fn end_step(&mut self, dt: f32) {
+        for state in self.states.values_mut() {
+            state.just_pressed = false;
+            state.just_released = false;
+
+            if state.down {
+                state.held += dt;
+            }
+        }
+    }
+}
+
+impl<T> Default for Buttons<T> {
+    fn default() -> Self {
+        Self {
+            states: HashMap::new(),
+        }
+    }
+}
+
+impl Framebuffer {
+    /// The input state for the current update step.
+    pub fn input(&self) -> &Input {
+        &self.main_window.input
+    }
+
+    pub fn key_just_pressed(&self, key: Key) -> bool {
+        self.input().key_just_pressed(key)
+    }
+
+    pub fn key_just_released(&self, key: Key) -> bool {
+        self.input().key_just_released(key)
+    }
+
+    pub fn key_held_for(&self, key: Key) -> f32 {
+        self.input().key_held_for(key)
+    }
+
+    pub fn mouse_button_just_pressed(&self, button: MouseButton) -> bool {
+        self.input().mouse_button_just_pressed(button)
+    }
+
+    pub fn mouse_button_just_released(&self, button: MouseButton) -> bool {
+        self.input().mouse_button_just_released(button)
+    }
+
+    pub fn mouse_button_held_for(&self, button: MouseButton) -> f32 {
+        self.input().mouse_button_held_for(button)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut Input, down: bool) {
+        input.set_key(Key::Space, Scancode::Space, down);
+    }
+
+    #[test]
+    fn edges_last_one_step() {
+        let mut input = Input::default();
+
+        press(&mut input, true);
+        assert!(input.key_pressed(Key::Space));
+        assert!(input.key_just_pressed(Key::Space));
+        assert!(input.scancode_just_pressed(Scancode::Space));
+
+        input.end_step(0.25);
+        assert!(input.key_pressed(Key::Space));
+        assert!(!input.key_just_pressed(Key::Space));
+
+        press(&mut input, false);
+        assert!(!input.key_pressed(Key::Space));
+        assert!(input.key_just_released(Key::Space));
+
+        input.end_step(0.25);
+        assert!(!input.key_just_released(Key::Space));
+        assert!(!input.scancode_just_released(Scancode::Space));
+    }
+
+    #[test]
+    fn press_and_release_in_one_step_reports_both() {
+        let mut input = Input::default();
+
+        input.set_mouse_button(MouseButton::Left, true);
+        input.set_mouse_button(MouseButton::Left, false);
+
+        assert!(!input.mouse_button_pressed(MouseButton::Left));
+        assert!(input.mouse_button_just_pressed(MouseButton::Left));
+        assert!(input.mouse_button_just_released(MouseButton::Left));
+        assert!(!input.mouse_button_just_pressed(MouseButton::Right));
+    }
+
+    #[test]
+    fn repeats_are_not_presses() {
+        let mut input = Input::default();
+
+        press(&mut input, true);
+        input.end_step(0.25);
+        press(&mut input, true);
+
+        assert!(!input.key_just_pressed(Key::Space));
+        assert_eq!(input.key_held_for(Key::Space), 0.25);
+    }
+
+    #[test]
+    fn held_for_counts_update_time_while_down() {
+        let mut input = Input::default();
+        assert_eq!(input.key_held_for(Key::Space), 0.);
+
+        press(&mut input, true);
+        assert_eq!(input.key_held_for(Key::Space), 0.);
+
+        input.end_step(0.25);
+        input.end_step(0.5);
+        assert_eq!(input.key_held_for(Key::Space), 0.75);
+        assert_eq!(input.scancode_held_for(Scancode::Space), 0.75);
+
+        press(&mut input, false);
+        assert_eq!(input.key_held_for(Key::Space), 0.);
+
+        input.end_step(0.25);
+        assert_eq!(input.key_held_for(Key::Space), 0.);
+    }
+}
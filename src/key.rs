@@ -1,8 +1,39 @@
-use sdl2_sys::SDL_KeyCode;
+use std::ops::{BitOr, BitOrAssign};
 
-#[repr(i32)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Key {
+use sdl2_sys::{SDL_KeyCode, SDL_Keymod, SDL_Scancode, SDLK_SCANCODE_MASK};
+
+macro_rules! keys {
+    ($($(#[$attr:meta])* $name:ident = $code:expr,)*) => {
+        /// A key by its meaning in the current keyboard layout. See [`crate::Scancode`] for
+        /// physical key positions.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Key {
+            $($(#[$attr])* $name,)*
+            /// A keycode without a variant, usually the character a key types on a non-US layout.
+            Other(i32),
+        }
+
+        impl Key {
+            /// Converts an SDL keycode. Never fails, keycodes without a variant become `Other`.
+            pub fn from_raw(raw: i32) -> Self {
+                $(if raw == $code {
+                    return Self::$name;
+                })*
+
+                Self::Other(raw)
+            }
+
+            pub fn to_raw(self) -> i32 {
+                match self {
+                    $(Self::$name => $code,)*
+                    Self::Other(raw) => raw,
+                }
+            }
+        }
+    };
+}
+
+keys! {
     Unknown = SDL_KeyCode::SDLK_UNKNOWN as i32,
     Return = SDL_KeyCode::SDLK_RETURN as i32,
     Escape = SDL_KeyCode::SDLK_ESCAPE as i32,
@@ -243,4 +274,147 @@ pub enum Key {
     App2 = SDL_KeyCode::SDLK_APP2 as i32,
     AudioRewind = SDL_KeyCode::SDLK_AUDIOREWIND as i32,
     AudioFastforward = SDL_KeyCode::SDLK_AUDIOFASTFORWARD as i32,
+    // Keys that don't produce a character use their scancode as keycode.
+    International1 = from_scancode(SDL_Scancode::SDL_SCANCODE_INTERNATIONAL1 as i32),
+    International2 = from_scancode(SDL_Scancode::SDL_SCANCODE_INTERNATIONAL2 as i32),
+    International3 = from_scancode(SDL_Scancode::SDL_SCANCODE_INTERNATIONAL3 as i32),
+    International4 = from_scancode(SDL_Scancode::SDL_SCANCODE_INTERNATIONAL4 as i32),
+    International5 = from_scancode(SDL_Scancode::SDL_SCANCODE_INTERNATIONAL5 as i32),
+    International6 = from_scancode(SDL_Scancode::SDL_SCANCODE_INTERNATIONAL6 as i32),
+    International7 = from_scancode(SDL_Scancode::SDL_SCANCODE_INTERNATIONAL7 as i32),
+    International8 = from_scancode(SDL_Scancode::SDL_SCANCODE_INTERNATIONAL8 as i32),
+    International9 = from_scancode(SDL_Scancode::SDL_SCANCODE_INTERNATIONAL9 as i32),
+    Lang1 = from_scancode(SDL_Scancode::SDL_SCANCODE_LANG1 as i32),
+    Lang2 = from_scancode(SDL_Scancode::SDL_SCANCODE_LANG2 as i32),
+    Lang3 = from_scancode(SDL_Scancode::SDL_SCANCODE_LANG3 as i32),
+    Lang4 = from_scancode(SDL_Scancode::SDL_SCANCODE_LANG4 as i32),
+    Lang5 = from_scancode(SDL_Scancode::SDL_SCANCODE_LANG5 as i32),
+    Lang6 = from_scancode(SDL_Scancode::SDL_SCANCODE_LANG6 as i32),
+    Lang7 = from_scancode(SDL_Scancode::SDL_SCANCODE_LANG7 as i32),
+    Lang8 = from_scancode(SDL_Scancode::SDL_SCANCODE_LANG8 as i32),
+    Lang9 = from_scancode(SDL_Scancode::SDL_SCANCODE_LANG9 as i32),
+    NonUsHash = from_scancode(SDL_Scancode::SDL_SCANCODE_NONUSHASH as i32),
+    NonUsBackslash = from_scancode(SDL_Scancode::SDL_SCANCODE_NONUSBACKSLASH as i32),
+    // Mobile keys added in SDL 2.28, newer than the bindings.
+    SoftLeft = from_scancode(287),
+    SoftRight = from_scancode(288),
+    Call = from_scancode(289),
+    EndCall = from_scancode(290),
+}
+
+/// The keycode SDL assigns to a key that has no character of its own.
+#[allow(clippy::cast_possible_wrap)]
+const fn from_scancode(scancode: i32) -> i32 {
+    scancode | SDLK_SCANCODE_MASK as i32
+}
+
+/// The modifier keys and lock states held during a key event. Combine with `|`; the unsided
+/// constants like [`Modifiers::SHIFT`] cover both the left and the right key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers(u16);
+
+impl Modifiers {
+    pub const NONE: Self = Self(0);
+    pub const LSHIFT: Self = Self(SDL_Keymod::KMOD_LSHIFT as u16);
+    pub const RSHIFT: Self = Self(SDL_Keymod::KMOD_RSHIFT as u16);
+    pub const LCTRL: Self = Self(SDL_Keymod::KMOD_LCTRL as u16);
+    pub const RCTRL: Self = Self(SDL_Keymod::KMOD_RCTRL as u16);
+    pub const LALT: Self = Self(SDL_Keymod::KMOD_LALT as u16);
+    pub const RALT: Self = Self(SDL_Keymod::KMOD_RALT as u16);
+    pub const LGUI: Self = Self(SDL_Keymod::KMOD_LGUI as u16);
+    pub const RGUI: Self = Self(SDL_Keymod::KMOD_RGUI as u16);
+    pub const NUM: Self = Self(SDL_Keymod::KMOD_NUM as u16);
+    pub const CAPS: Self = Self(SDL_Keymod::KMOD_CAPS as u16);
+    pub const SHIFT: Self = Self(Self::LSHIFT.0 | Self::RSHIFT.0);
+    pub const CTRL: Self = Self(Self::LCTRL.0 | Self::RCTRL.0);
+    pub const ALT: Self = Self(Self::LALT.0 | Self::RALT.0);
+    pub const GUI: Self = Self(Self::LGUI.0 | Self::RGUI.0);
+
+    /// Keeps the bits of an SDL `Keymod` that have a constant here.
+    pub fn from_raw(raw: u16) -> Self {
+        let known = Self::SHIFT | Self::CTRL | Self::ALT | Self::GUI | Self::NUM | Self::CAPS;
+
+        Self(raw & known.0)
+    }
+
+    pub fn bits(self) -> u16 {
+        self.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether any of the modifiers in `other` is held, so `intersects(Modifiers::SHIFT)` is true
+    /// for either shift key.
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Whether all of the modifiers in `other` are held. Unsided constants need both keys, so
+    /// check a single combination like Ctrl+Shift with [`Modifiers::ctrl`] and
+    /// [`Modifiers::shift`] instead.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn shift(self) -> bool {
+        self.intersects(Self::SHIFT)
+    }
+
+    pub fn ctrl(self) -> bool {
+        self.intersects(Self::CTRL)
+    }
+
+    pub fn alt(self) -> bool {
+        self.intersects(Self::ALT)
+    }
+
+    pub fn gui(self) -> bool {
+        self.intersects(Self::GUI)
+    }
+
+    pub fn caps_lock(self) -> bool {
+        self.intersects(Self::CAPS)
+    }
+
+    pub fn num_lock(self) -> bool {
+        self.intersects(Self::NUM)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_needs_all_intersects_needs_any() {
+        let mods = Modifiers::LSHIFT | Modifiers::LCTRL;
+
+        assert!(mods.contains(Modifiers::LSHIFT | Modifiers::LCTRL));
+        assert!(!mods.contains(Modifiers::LSHIFT | Modifiers::LALT));
+        assert!(!mods.contains(Modifiers::SHIFT));
+        assert!(mods.contains(Modifiers::NONE));
+
+        assert!(mods.intersects(Modifiers::SHIFT));
+        assert!(mods.intersects(Modifiers::CTRL | Modifiers::ALT));
+        assert!(!mods.intersects(Modifiers::ALT | Modifiers::GUI));
+
+        assert!(mods.shift() && mods.ctrl() && !mods.alt());
+        assert!(!Modifiers::LSHIFT.contains(Modifiers::CTRL | Modifiers::SHIFT));
+    }
 }
@@ -1,18 +1,51 @@
 #![allow(clippy::missing_const_for_fn, clippy::must_use_candidate)]
 
+mod affine;
+mod blend;
+mod clock;
+pub mod codec;
+mod depth;
+mod error;
+mod gamepad;
+mod image;
+mod input;
 pub mod key;
+mod mouse;
+mod options;
+mod raster;
+mod record;
+pub mod scancode;
+mod shapes;
+pub mod snapshot;
 mod text_renderer;
 
-use std::collections::HashMap;
+use std::cell::UnsafeCell;
 use std::ffi::{c_int as int, CStr, CString};
 use std::mem::{size_of, ManuallyDrop, MaybeUninit};
+use std::path::PathBuf;
 use std::ptr;
-use std::slice;
+use std::slice::{self, SliceIndex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[allow(clippy::wildcard_imports)]
 use sdl2_sys::*;
 
-pub use key::Key;
+pub use affine::{Affine, Sampling};
+pub use blend::BlendMode;
+pub use clock::{Clock, FrameInfo};
+pub use codec::ImageFormat;
+pub use error::{Error, IoError};
+use gamepad::Gamepads;
+pub use gamepad::{GamepadAxis, GamepadButton, GamepadId, GamepadStick};
+pub use image::{BlitOptions, Image, Rotation};
+pub use input::Input;
+pub use key::{Key, Modifiers};
+pub use mouse::MouseButton;
+pub use options::{FrameLimit, Fullscreen, ResizePolicy, WindowOptions};
+pub use raster::FillRule;
+pub use record::RecordFormat;
+use record::Recorder;
+pub use scancode::Scancode;
 use text_renderer::TextRenderer;
 
 pub struct Framebuffer {
@@ -20,29 +53,123 @@ pub struct Framebuffer {
     running: bool,
     dt: f32,
     fps_buf: FpsCounter,
+    clock: Clock,
+    frame_limit: FrameLimit,
+    screenshot: Option<PathBuf>,
+    screenshot_key: Option<Key>,
+    recorder: Option<Recorder>,
+    gamepads: Gamepads,
+    close_requested: bool,
 }
 
 pub struct Window {
     width: u32,
     height: u32,
-    handle: *mut SDL_Window,
-    renderer: *mut SDL_Renderer,
-    texture: *mut SDL_Texture,
-    _id: u32,
-    key_pressed: HashMap<Key, bool>,
+    backend: Backend,
+    depth: UnsafeCell<Vec<f32>>,
+    id: u32,
+    input: Input,
     title: &'static str,
+    options: WindowOptions,
+    fullscreen: Fullscreen,
 }
 
 pub struct DrawHandle<'p> {
     pixels: &'p mut [u32],
+    depth: &'p mut Vec<f32>,
+    blend_mode: BlendMode,
     window: &'p Window,
+    frame: FrameInfo,
 }
 
 #[derive(Debug)]
 pub enum Event {
-    KeyPress(Key),
-    KeyRelease(Key),
-    MouseMove(i32, i32),
+    /// `key` depends on the keyboard layout, `scancode` is the physical position of the key.
+    /// `repeat` is set for the presses generated while a key is held down.
+    KeyPress {
+        key: Key,
+        scancode: Scancode,
+        modifiers: Modifiers,
+        repeat: bool,
+    },
+    KeyRelease {
+        key: Key,
+        scancode: Scancode,
+        modifiers: Modifiers,
+    },
+    /// Absolute position and the motion since the last event, in buffer pixels even when the
+    /// buffer is scaled or letterboxed.
+    MouseMove {
+        x: i32,
+        y: i32,
+        xrel: i32,
+        yrel: i32,
+    },
+    /// `clicks` is 1 for a single click, 2 for a double click and so on. The position is in
+    /// buffer pixels, like for [`Event::MouseMove`].
+    MouseDown {
+        button: MouseButton,
+        clicks: u8,
+        x: i32,
+        y: i32,
+    },
+    /// Fields as for [`Event::MouseDown`].
+    MouseUp {
+        button: MouseButton,
+        clicks: u8,
+        x: i32,
+        y: i32,
+    },
+    /// Scroll amount, positive to the right and away from the user. Fractional for trackpads and
+    /// high resolution wheels.
+    MouseWheel {
+        x: f32,
+        y: f32,
+    },
+    /// Text typed while text input is enabled, already composed by the keyboard layout or IME.
+    TextInput(String),
+    /// The IME's in-progress composition changed. `start` and `len` give the selected part of
+    /// `text`, in characters.
+    TextEditing {
+        text: String,
+        start: i32,
+        len: i32,
+    },
+    /// Also sent at startup for gamepads that are already connected.
+    GamepadConnected(GamepadId),
+    GamepadDisconnected(GamepadId),
+    GamepadButtonDown {
+        id: GamepadId,
+        button: GamepadButton,
+    },
+    GamepadButtonUp {
+        id: GamepadId,
+        button: GamepadButton,
+    },
+    /// `value` has the deadzone applied, see [`Framebuffer::set_gamepad_deadzone`].
+    GamepadAxisMotion {
+        id: GamepadId,
+        axis: GamepadAxis,
+        value: f32,
+    },
+    /// The window's drawable area changed size, in window coordinates. The first field of this and
+    /// the other window events is the id of the window, see [`Window::id`].
+    Resized {
+        window: u32,
+        width: u32,
+        height: u32,
+    },
+    FocusGained(u32),
+    FocusLost(u32),
+    Minimized(u32),
+    /// The window was restored from being minimized or maximized.
+    Restored(u32),
+    /// Part of the window was uncovered and may need to be redrawn.
+    Exposed(u32),
+    /// The user asked to close a window, or the process got a quit signal. For the main window
+    /// the framebuffer stops running after the event unless [`Framebuffer::cancel_close`] is
+    /// called while handling it. Other windows are never closed automatically.
+    CloseRequested(u32),
 }
 
 pub trait MainLoop {
@@ -51,8 +178,19 @@ pub trait MainLoop {
     fn render(&mut self, d: &mut DrawHandle);
 }
 
-trait CheckErr {
-    fn check_err(self, action: &'static str) -> Self;
+trait CheckErr: Sized {
+    fn check_err(self, err: fn(String) -> Error) -> Result<Self, Error>;
+}
+
+enum Backend {
+    Sdl {
+        handle: *mut SDL_Window,
+        renderer: *mut SDL_Renderer,
+        texture: *mut SDL_Texture,
+    },
+    Headless {
+        pixels: UnsafeCell<Vec<u32>>,
+    },
 }
 
 struct FpsCounter {
@@ -65,14 +203,7 @@ const TEXT_RENDERER: TextRenderer = TextRenderer::new();
 
 impl Framebuffer {
     pub fn new(width: u32, height: u32, title: &'static str, update_rate: i16) -> Self {
-        init_library();
-
-        Self {
-            main_window: ManuallyDrop::new(Window::new(width, height, title)),
-            running: true,
-            dt: 1. / f32::from(update_rate),
-            fps_buf: FpsCounter::new(32),
-        }
+        Self::try_new(width, height, title, update_rate).unwrap_or_else(|err| panic!("{err}"))
     }
 
     pub fn with_pos(
@@ -83,18 +214,103 @@ impl Framebuffer {
         title: &'static str,
         update_rate: i16,
     ) -> Self {
-        init_library();
+        Self::try_with_pos(x, y, width, height, title, update_rate)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn with_options(
+        width: u32,
+        height: u32,
+        title: &'static str,
+        update_rate: i16,
+        options: WindowOptions,
+    ) -> Self {
+        Self::try_with_options(width, height, title, update_rate, options)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn try_new(
+        width: u32,
+        height: u32,
+        title: &'static str,
+        update_rate: i16,
+    ) -> Result<Self, Error> {
+        Self::try_with_options(width, height, title, update_rate, WindowOptions::default())
+    }
+
+    pub fn try_with_pos(
+        x: Option<u32>,
+        y: Option<u32>,
+        width: u32,
+        height: u32,
+        title: &'static str,
+        update_rate: i16,
+    ) -> Result<Self, Error> {
+        let options = WindowOptions {
+            x,
+            y,
+            ..WindowOptions::default()
+        };
 
+        Self::try_with_options(width, height, title, update_rate, options)
+    }
+
+    pub fn try_with_options(
+        width: u32,
+        height: u32,
+        title: &'static str,
+        update_rate: i16,
+        options: WindowOptions,
+    ) -> Result<Self, Error> {
+        init_library()?;
+
+        let window = Window::try_with_options(width, height, title, options)
+            .inspect_err(|_| unsafe { SDL_Quit() })?;
+
+        Ok(Self {
+            main_window: ManuallyDrop::new(window),
+            running: true,
+            dt: 1. / f32::from(update_rate),
+            fps_buf: FpsCounter::new(32),
+            clock: Clock::real(),
+            frame_limit: FrameLimit::default(),
+            screenshot: Option::None,
+            screenshot_key: Option::None,
+            recorder: Option::None,
+            gamepads: Gamepads::new(),
+            close_requested: false,
+        })
+    }
+
+    /// Creates a framebuffer that renders into memory instead of a window. SDL is not
+    /// initialized, no events are delivered and time only advances as frames are rendered.
+    pub fn headless(width: u32, height: u32, update_rate: i16) -> Self {
         Self {
-            main_window: ManuallyDrop::new(Window::with_pos(x, y, width, height, title)),
+            main_window: ManuallyDrop::new(Window::headless(width, height)),
             running: true,
             dt: 1. / f32::from(update_rate),
             fps_buf: FpsCounter::new(32),
+            clock: Clock::simulated(),
+            frame_limit: FrameLimit::default(),
+            screenshot: Option::None,
+            screenshot_key: Option::None,
+            recorder: Option::None,
+            gamepads: Gamepads::new(),
+            close_requested: false,
         }
     }
 
-    fn poll_events(&mut self, state: &mut impl MainLoop) {
+    pub fn is_headless(&self) -> bool {
+        self.main_window.is_headless()
+    }
+
+    fn poll_events(&mut self, state: &mut impl MainLoop) -> Result<(), Error> {
+        if self.is_headless() {
+            return Ok(());
+        }
+
         let mut event_ptr = MaybeUninit::<SDL_Event>::uninit();
+        let mut main_window_closed = false;
 
         loop {
             unsafe {
@@ -103,102 +319,343 @@ impl Framebuffer {
                 }
 
                 let event = event_ptr.assume_init();
-                let type_ = std::mem::transmute::<u32, SDL_EventType>(event.type_);
+                let type_ = event.type_;
 
                 match type_ {
-                    SDL_EventType::SDL_KEYDOWN => {
-                        let key = std::mem::transmute::<i32, Key>(event.key.keysym.sym);
-                        let event = Event::KeyPress(key);
-                        self.main_window
-                            .key_pressed
-                            .entry(key)
-                            .and_modify(|pr| *pr = true)
-                            .or_insert(true);
+                    event_type::KEYDOWN => {
+                        let key = Key::from_raw(event.key.keysym.sym);
+                        let repeat = event.key.repeat != 0;
+
+                        if self.screenshot_key == Some(key) {
+                            if !repeat {
+                                self.screenshot = Some(screenshot_file_name().into());
+                            }
+                            continue;
+                        }
+
+                        let scancode = raw_scancode(&event.key.keysym);
+                        let event = Event::KeyPress {
+                            key,
+                            scancode,
+                            modifiers: Modifiers::from_raw(event.key.keysym.mod_),
+                            repeat,
+                        };
+                        self.main_window.input.set_key(key, scancode, true);
+                        state.handle_event(self, &event);
+                    }
+                    event_type::KEYUP => {
+                        let key = Key::from_raw(event.key.keysym.sym);
+
+                        if self.screenshot_key == Some(key) {
+                            continue;
+                        }
+
+                        let scancode = raw_scancode(&event.key.keysym);
+                        let event = Event::KeyRelease {
+                            key,
+                            scancode,
+                            modifiers: Modifiers::from_raw(event.key.keysym.mod_),
+                        };
+                        self.main_window.input.set_key(key, scancode, false);
+                        state.handle_event(self, &event);
+                    }
+                    event_type::MOUSEMOTION => {
+                        let motion = event.motion;
+                        let event = Event::MouseMove {
+                            x: motion.x,
+                            y: motion.y,
+                            xrel: motion.xrel,
+                            yrel: motion.yrel,
+                        };
+                        state.handle_event(self, &event);
+                    }
+                    event_type::MOUSEBUTTONDOWN | event_type::MOUSEBUTTONUP => {
+                        let button_event = event.button;
+                        let button = MouseButton::from_raw(button_event.button);
+                        let pressed = type_ == event_type::MOUSEBUTTONDOWN;
+                        let (clicks, x, y) = (button_event.clicks, button_event.x, button_event.y);
+                        let event = if pressed {
+                            Event::MouseDown {
+                                button,
+                                clicks,
+                                x,
+                                y,
+                            }
+                        } else {
+                            Event::MouseUp {
+                                button,
+                                clicks,
+                                x,
+                                y,
+                            }
+                        };
+                        self.main_window.input.set_mouse_button(button, pressed);
                         state.handle_event(self, &event);
                     }
-                    SDL_EventType::SDL_KEYUP => {
-                        let key = std::mem::transmute::<i32, Key>(event.key.keysym.sym);
-                        let event = Event::KeyRelease(key);
-                        self.main_window
-                            .key_pressed
-                            .entry(key)
-                            .and_modify(|pr| *pr = false)
-                            .or_insert(false);
+                    event_type::MOUSEWHEEL => {
+                        let event = wheel_event(&event.wheel);
                         state.handle_event(self, &event);
                     }
-                    SDL_EventType::SDL_MOUSEMOTION => {
-                        let event = Event::MouseMove(event.motion.xrel, event.motion.yrel);
+                    event_type::TEXTINPUT => {
+                        let text = CStr::from_ptr(event.text.text.as_ptr());
+                        let event = Event::TextInput(text.to_string_lossy().into_owned());
                         state.handle_event(self, &event);
                     }
-                    SDL_EventType::SDL_QUIT => self.running = false,
+                    event_type::TEXTEDITING => {
+                        let edit = event.edit;
+                        let text = CStr::from_ptr(edit.text.as_ptr());
+                        let event = Event::TextEditing {
+                            text: text.to_string_lossy().into_owned(),
+                            start: edit.start,
+                            len: edit.length,
+                        };
+                        state.handle_event(self, &event);
+                    }
+                    event_type::CONTROLLERDEVICEADDED
+                    | event_type::CONTROLLERDEVICEREMOVED
+                    | event_type::CONTROLLERBUTTONDOWN
+                    | event_type::CONTROLLERBUTTONUP
+                    | event_type::CONTROLLERAXISMOTION => {
+                        if let Some(event) = self.gamepads.handle_event(&event) {
+                            state.handle_event(self, &event);
+                        }
+                    }
+                    event_type::WINDOWEVENT => {
+                        let window = event.window;
+
+                        if window.event == SDL_WindowEventID::SDL_WINDOWEVENT_CLOSE as u8 {
+                            main_window_closed |= window.windowID == self.main_window.id;
+                            self.request_close(state, window.windowID);
+                        } else if let Some(event) = window_event(&window) {
+                            if let Event::Resized {
+                                window,
+                                width,
+                                height,
+                            } = event
+                            {
+                                self.follow_resize(window, width, height)?;
+                            }
+
+                            state.handle_event(self, &event);
+                        }
+                    }
+                    // Older SDL versions follow closing the last window with a quit event.
+                    event_type::QUIT if !main_window_closed => {
+                        self.request_close(state, self.main_window.id);
+                    }
                     _ => (),
                 }
             }
         }
+
+        Ok(())
+    }
+
+    fn follow_resize(&mut self, window_id: u32, width: u32, height: u32) -> Result<(), Error> {
+        let window = &mut self.main_window;
+        let follows = window.options.resize_policy == ResizePolicy::ResizeBuffer;
+
+        let scale = window.options.scale.max(1);
+        let (width, height) = (width / scale, height / scale);
+
+        // Minimized windows may report a zero size, fullscreen scales instead.
+        if window_id != window.id || !follows || window.is_fullscreen() || width == 0 || height == 0
+        {
+            return Ok(());
+        }
+
+        if (width, height) == (window.width, window.height) {
+            return Ok(());
+        }
+
+        window.resize_buffer(width, height)?;
+
+        if let Err(err) = self.stop_recording() {
+            eprintln!("Failed to finish recording: {err}");
+        }
+
+        Ok(())
+    }
+
+    fn request_close(&mut self, state: &mut impl MainLoop, window_id: u32) {
+        let is_main = window_id == self.main_window.id;
+
+        self.close_requested = is_main;
+        state.handle_event(self, &Event::CloseRequested(window_id));
+
+        if is_main && self.close_requested {
+            self.running = false;
+        }
+
+        self.close_requested = false;
+    }
+
+    fn advance_clock(&mut self) -> f64 {
+        self.clock.start_frame(f64::from(self.dt))
+    }
+
+    fn update_until(
+        &mut self,
+        state: &mut impl MainLoop,
+        current_time: &mut f64,
+        real_time: f64,
+    ) -> Result<u32, Error> {
+        let mut ticks = 0;
+
+        while *current_time < real_time {
+            *current_time += f64::from(self.dt);
+
+            self.poll_events(state)?;
+            state.update(self, self.dt, *current_time);
+            self.main_window.input.end_step(self.dt);
+            ticks += 1;
+        }
+
+        Ok(ticks)
+    }
+
+    fn render_frame(
+        &mut self,
+        state: &mut impl MainLoop,
+        ticks: u32,
+        frame: FrameInfo,
+    ) -> Result<(), Error> {
+        let mut handle = self.main_window.start_render()?;
+        handle.frame = frame;
+        state.render(&mut handle);
+
+        if let Some(path) = self.screenshot.take() {
+            // Screenshots are a debugging aid and must not end the run.
+            if let Err(err) = handle.snapshot().save(&path) {
+                eprintln!("Failed to save screenshot {}: {err}", path.display());
+            }
+        }
+
+        let recorded = match &mut self.recorder {
+            Some(recorder) => recorder.write_frame(handle.pixels, ticks),
+            Option::None => Ok(()),
+        };
+
+        self.main_window.present();
+
+        // Like screenshots, a broken recording must not end the run.
+        if let Err(err) = recorded {
+            eprintln!("Recording stopped: {err}");
+            let _ = self.stop_recording();
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn frame_info(&self, current_time: f64, real_time: f64) -> FrameInfo {
+        let dt = f64::from(self.dt);
+
+        FrameInfo {
+            alpha: (1. - (current_time - real_time) / dt).clamp(0., 1.) as f32,
+            delta: self.clock.frame_delta(),
+            time: real_time,
+        }
     }
 
-    fn show_fps(&mut self, real_time: f64) {
-        let elapsed = current_time_seconds() - real_time;
-        let average = self.fps_buf.add_measurement(1. / elapsed);
+    fn limit_fps(&self) {
+        let FrameLimit::Fps(target_fps) = self.frame_limit else {
+            return;
+        };
+
+        if target_fps.is_finite() && target_fps > 0. {
+            self.clock
+                .wait_until(self.clock.frame_start() + 1. / target_fps);
+        }
+    }
+
+    fn show_fps(&mut self) {
+        if self.is_headless() {
+            return;
+        }
+
+        let average = self.fps_buf.add_measurement(1. / self.clock.elapsed());
 
         self.set_window_title(&format!("{} FPS {:5.3}", self.main_window.title, average));
     }
 
     pub fn run(&mut self, state: &mut impl MainLoop) {
-        let mut current_time = current_time_seconds();
+        self.try_run(state).unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    pub fn try_run(&mut self, state: &mut impl MainLoop) -> Result<(), Error> {
+        let mut current_time = self.clock.now();
 
         while self.running {
-            let real_time = current_time_seconds();
+            let real_time = self.advance_clock();
 
-            while current_time < real_time {
-                current_time += f64::from(self.dt);
-
-                self.poll_events(state);
-                state.update(self, self.dt, current_time);
-            }
+            let ticks = self.update_until(state, &mut current_time, real_time)?;
 
             if !self.running {
                 break;
             }
 
-            let mut handle = self.main_window.start_render();
-            state.render(&mut handle);
-            self.main_window.present();
+            let info = self.frame_info(current_time, real_time);
+            self.render_frame(state, ticks, info)?;
 
-            limit_fps(500.0, real_time);
-            self.show_fps(real_time);
+            self.limit_fps();
+            self.show_fps();
         }
+
+        Ok(())
     }
 
     pub fn benchmark(&mut self, state: &mut impl MainLoop, frames: usize) {
+        self.try_run_frames(state, frames, true)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    /// Runs at most `frames` frames, stopping early if the state closes the framebuffer. Combined
+    /// with [`Framebuffer::headless`] this advances the simulated clock by one update per frame.
+    pub fn run_frames(&mut self, state: &mut impl MainLoop, frames: usize) {
+        self.try_run_frames(state, frames, false)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    fn try_run_frames(
+        &mut self,
+        state: &mut impl MainLoop,
+        frames: usize,
+        show_progress: bool,
+    ) -> Result<(), Error> {
         let title = self.main_window.title;
-        let mut current_time = current_time_seconds();
+        let mut current_time = self.clock.now();
         let mut frame = 0;
 
         while self.running && frame < frames {
-            let real_time = current_time_seconds();
-
-            while current_time < real_time {
-                current_time += f64::from(self.dt);
+            let real_time = self.advance_clock();
 
-                self.poll_events(state);
-                state.update(self, self.dt, current_time);
-            }
+            let ticks = self.update_until(state, &mut current_time, real_time)?;
 
             if !self.running {
                 break;
             }
 
-            let mut handle = self.main_window.start_render();
-            state.render(&mut handle);
-            self.main_window.present();
+            let info = self.frame_info(current_time, real_time);
+            self.render_frame(state, ticks, info)?;
 
-            limit_fps(500.0, real_time);
+            self.limit_fps();
 
             frame += 1;
 
-            self.set_window_title(&format!("{} frame {}/{}", title, frame, frames));
+            if show_progress {
+                self.set_window_title(&format!("{} frame {}/{}", title, frame, frames));
+            }
         }
+
+        Ok(())
+    }
+
+    /// Returns the pixels of the last rendered frame of a headless framebuffer. A window's pixels
+    /// live in a texture that is only mapped while a frame is drawn, capture them with
+    /// [`Framebuffer::screenshot`] or [`DrawHandle::snapshot`] instead.
+    pub fn pixels(&self) -> Option<&[u32]> {
+        self.main_window.pixels()
     }
 
     pub fn width(&self) -> u32 {
@@ -223,10 +680,54 @@ impl Framebuffer {
         self.running = false;
     }
 
+    /// Keeps the framebuffer running after an [`Event::CloseRequested`] for the main window, for
+    /// example to ask about unsaved changes first. Does nothing outside of handling that event.
+    pub fn cancel_close(&mut self) {
+        self.close_requested = false;
+    }
+
+    /// The id of the main window, as found in window events.
+    pub fn window_id(&self) -> u32 {
+        self.main_window.id
+    }
+
     pub fn set_window_title(&mut self, title: &str) {
         self.main_window.set_window_title(title);
     }
 
+    pub fn set_fullscreen(&mut self, enabled: bool) {
+        self.main_window.set_fullscreen(enabled);
+    }
+
+    pub fn try_set_fullscreen(&mut self, enabled: bool) -> Result<(), Error> {
+        self.main_window.try_set_fullscreen(enabled)
+    }
+
+    pub fn set_vsync(&mut self, enabled: bool) {
+        self.main_window.set_vsync(enabled);
+    }
+
+    pub fn try_set_vsync(&mut self, enabled: bool) -> Result<(), Error> {
+        self.main_window.try_set_vsync(enabled)
+    }
+
+    /// Replaces the default cap of 500 frames per second. Headless framebuffers never wait.
+    pub fn set_frame_limit(&mut self, limit: FrameLimit) {
+        self.frame_limit = limit;
+    }
+
+    pub fn frame_limit(&self) -> FrameLimit {
+        self.frame_limit
+    }
+
+    pub fn toggle_fullscreen(&mut self) {
+        self.set_fullscreen(!self.is_fullscreen());
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.main_window.is_fullscreen()
+    }
+
     pub fn grab_mouse(&mut self, enabled: bool) {
         set_mouse_grab(enabled);
     }
@@ -236,23 +737,97 @@ impl Framebuffer {
     }
 
     pub fn mouse_pos(&self) -> (i32, i32) {
-        mouse_pos()
+        self.main_window.mouse_pos()
     }
 
     pub fn mouse_pos_rel(&self) -> (i32, i32) {
-        mouse_pos_rel()
+        self.main_window.mouse_pos_rel()
+    }
+
+    /// Starts delivering [`Event::TextInput`] and [`Event::TextEditing`] and shows the IME or
+    /// on-screen keyboard, if any. Key events keep being delivered. Off by default.
+    pub fn start_text_input(&mut self) {
+        if !self.is_headless() {
+            unsafe { SDL_StartTextInput() };
+        }
+    }
+
+    pub fn stop_text_input(&mut self) {
+        if !self.is_headless() {
+            unsafe { SDL_StopTextInput() };
+        }
+    }
+
+    pub fn text_input_active(&self) -> bool {
+        !self.is_headless() && unsafe { SDL_IsTextInputActive() } == SDL_bool::SDL_TRUE
+    }
+
+    /// Tells the IME where the text being typed is, in window coordinates, so its candidate
+    /// window can be placed next to it.
+    pub fn set_text_input_rect(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        if self.is_headless() {
+            return;
+        }
+
+        let mut rect = SDL_Rect {
+            x,
+            y,
+            w: width as int,
+            h: height as int,
+        };
+
+        unsafe { SDL_SetTextInputRect(&mut rect) };
     }
 
     pub fn key_pressed(&self, key: Key) -> bool {
         self.main_window.key_pressed(key)
     }
+
+    pub fn scancode_pressed(&self, scancode: Scancode) -> bool {
+        self.main_window.scancode_pressed(scancode)
+    }
+
+    pub fn mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.main_window.mouse_button_pressed(button)
+    }
+
+    /// Saves the next rendered frame to `path`, in the format matching its extension. Fails right
+    /// away if that format is unknown or wasn't compiled in. Failing to write the file later is
+    /// printed to stderr and doesn't stop the run.
+    pub fn screenshot(&mut self, path: impl Into<PathBuf>) -> Result<(), Error> {
+        let path = path.into();
+        let format = ImageFormat::from_path(&path)
+            .ok_or_else(|| Error::UnsupportedFormat(path.display().to_string()))?;
+
+        if !format.is_enabled() {
+            return Err(format.unsupported());
+        }
+
+        self.screenshot = Some(path);
+
+        Ok(())
+    }
+
+    /// Makes `key` save the next frame as `screenshot-<unix time in ms>.<ext>` in the working
+    /// directory. The key is no longer delivered to [`MainLoop::handle_event`]. Disabled by
+    /// default.
+    pub fn set_screenshot_key(&mut self, key: Option<Key>) {
+        self.screenshot_key = key;
+    }
 }
 
 impl Drop for Framebuffer {
     fn drop(&mut self) {
+        let headless = self.is_headless();
+        let _ = self.stop_recording();
+
         unsafe {
             ManuallyDrop::drop(&mut self.main_window);
-            SDL_Quit();
+
+            if !headless {
+                self.gamepads.close_all();
+                SDL_Quit();
+            }
         }
     }
 }
@@ -269,97 +844,420 @@ impl Window {
         height: u32,
         title: &'static str,
     ) -> Self {
-        let x = x.map(|x| x as int);
-        let y = y.map(|y| y as int);
+        Self::try_with_pos(x, y, width, height, title).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn with_options(
+        width: u32,
+        height: u32,
+        title: &'static str,
+        options: WindowOptions,
+    ) -> Self {
+        Self::try_with_options(width, height, title, options).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn try_new(width: u32, height: u32, title: &'static str) -> Result<Self, Error> {
+        Self::try_with_options(width, height, title, WindowOptions::default())
+    }
+
+    pub fn try_with_pos(
+        x: Option<u32>,
+        y: Option<u32>,
+        width: u32,
+        height: u32,
+        title: &'static str,
+    ) -> Result<Self, Error> {
+        let options = WindowOptions {
+            x,
+            y,
+            ..WindowOptions::default()
+        };
+
+        Self::try_with_options(width, height, title, options)
+    }
+
+    pub fn try_with_options(
+        width: u32,
+        height: u32,
+        title: &'static str,
+        options: WindowOptions,
+    ) -> Result<Self, Error> {
+        let x = options.x.map(|x| x as int);
+        let y = options.y.map(|y| y as int);
         let w_int = width as int;
         let h_int = height as int;
-        let handle = create_window(x, y, w_int, h_int, title);
-        let renderer = create_renderer(handle);
-        let texture = create_texture(renderer, w_int, h_int);
+        let scale = options.scale.max(1) as int;
+        let mut flags = 0;
+
+        if options.resizable {
+            flags |= SDL_WindowFlags::SDL_WINDOW_RESIZABLE as u32;
+        }
+
+        if options.borderless {
+            flags |= SDL_WindowFlags::SDL_WINDOW_BORDERLESS as u32;
+        }
+
+        flags |= fullscreen_flags(options.fullscreen);
+
+        let handle = create_window(x, y, w_int * scale, h_int * scale, title, flags)?;
+        let renderer = create_renderer(handle, options.vsync)
+            .inspect_err(|_| unsafe { SDL_DestroyWindow(handle) })?;
+        let texture = create_texture(renderer, w_int, h_int).inspect_err(|_| unsafe {
+            SDL_DestroyRenderer(renderer);
+            SDL_DestroyWindow(handle);
+        })?;
         let id = get_window_id(handle);
-        let key_pressed = HashMap::with_capacity(240);
 
-        Self {
+        if scale > 1 {
+            unsafe { SDL_RenderSetIntegerScale(renderer, SDL_bool::SDL_TRUE) };
+        }
+
+        let window = Self {
             width,
             height,
-            handle,
-            renderer,
-            texture,
-            _id: id,
-            key_pressed,
+            backend: Backend::Sdl {
+                handle,
+                renderer,
+                texture,
+            },
+            depth: UnsafeCell::new(Vec::new()),
+            id,
+            input: Input::default(),
             title,
+            options,
+            fullscreen: options.fullscreen,
+        };
+
+        window.update_logical_size();
+
+        Ok(window)
+    }
+
+    /// Creates a window-less render target backed by an in-memory pixel buffer.
+    pub fn headless(width: u32, height: u32) -> Self {
+        let num_pixels = (width * height) as usize;
+
+        Self {
+            width,
+            height,
+            backend: Backend::Headless {
+                pixels: UnsafeCell::new(vec![0; num_pixels]),
+            },
+            depth: UnsafeCell::new(Vec::new()),
+            id: 0,
+            input: Input::default(),
+            title: "",
+            options: WindowOptions::default(),
+            fullscreen: Fullscreen::Off,
         }
     }
 
-    fn start_render(&self) -> DrawHandle {
+    pub fn is_headless(&self) -> bool {
+        matches!(self.backend, Backend::Headless { .. })
+    }
+
+    /// The id SDL assigned to the window, 0 for headless windows.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Reallocates the pixel buffer, discarding its contents. The main window of a
+    /// [`Framebuffer`] does this on its own when resized with [`ResizePolicy::ResizeBuffer`],
+    /// other windows have to call it when they get an [`Event::Resized`].
+    pub fn resize_buffer(&mut self, width: u32, height: u32) -> Result<(), Error> {
+        match &mut self.backend {
+            Backend::Sdl {
+                renderer, texture, ..
+            } => {
+                let new_texture = create_texture(*renderer, width as int, height as int)?;
+
+                unsafe { SDL_DestroyTexture(*texture) };
+                *texture = new_texture;
+            }
+            Backend::Headless { pixels } => pixels.get_mut().resize((width * height) as usize, 0),
+        }
+
+        let depth = self.depth.get_mut();
+        if !depth.is_empty() {
+            depth.clear();
+            depth.resize((width * height) as usize, f32::INFINITY);
+        }
+
+        self.width = width;
+        self.height = height;
+        self.update_logical_size();
+
+        Ok(())
+    }
+
+    pub fn set_fullscreen(&mut self, enabled: bool) {
+        self.try_set_fullscreen(enabled)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    /// Switches to the fullscreen kind given in [`WindowOptions::fullscreen`], or to
+    /// [`Fullscreen::Desktop`] if that is [`Fullscreen::Off`], or back to a window.
+    pub fn try_set_fullscreen(&mut self, enabled: bool) -> Result<(), Error> {
+        let Backend::Sdl { handle, .. } = self.backend else {
+            return Ok(());
+        };
+
+        let mode = match (enabled, self.options.fullscreen) {
+            (false, _) => Fullscreen::Off,
+            (true, Fullscreen::Off) => Fullscreen::Desktop,
+            (true, mode) => mode,
+        };
+
+        unsafe { SDL_SetWindowFullscreen(handle, fullscreen_flags(mode)) }
+            .check_err(Error::DisplayMode)?;
+
+        self.fullscreen = mode;
+        self.update_logical_size();
+
+        Ok(())
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen != Fullscreen::Off
+    }
+
+    pub fn set_vsync(&mut self, enabled: bool) {
+        self.try_set_vsync(enabled)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    pub fn try_set_vsync(&mut self, enabled: bool) -> Result<(), Error> {
+        let Backend::Sdl { renderer, .. } = self.backend else {
+            return Ok(());
+        };
+
+        unsafe { SDL_RenderSetVSync(renderer, int::from(enabled)) }.check_err(Error::VSync)?;
+        self.options.vsync = enabled;
+
+        Ok(())
+    }
+
+    fn update_logical_size(&self) {
+        let Backend::Sdl { renderer, .. } = self.backend else {
+            return;
+        };
+
+        let scaled = self.options.scale > 1
+            || self.options.resize_policy == ResizePolicy::FixedResolution
+            || self.is_fullscreen();
+        let (w, h) = if scaled {
+            (self.width as int, self.height as int)
+        } else {
+            (0, 0)
+        };
+
+        unsafe { SDL_RenderSetLogicalSize(renderer, w, h) };
+    }
+
+    /// Returns the pixels of the last rendered frame, or `None` for SDL-backed windows, see
+    /// [`Framebuffer::pixels`].
+    pub fn pixels(&self) -> Option<&[u32]> {
+        match &self.backend {
+            Backend::Sdl { .. } => Option::None,
+            Backend::Headless { pixels } => Some(unsafe { &*pixels.get() }),
+        }
+    }
+
+    fn start_render(&mut self) -> Result<DrawHandle<'_>, Error> {
+        // Sound because `&mut self` stays borrowed for as long as the handle lives.
+        let window = &*self;
+        let pixels = match &window.backend {
+            Backend::Sdl { texture, .. } => window.lock_texture(*texture)?,
+            Backend::Headless { pixels } => unsafe { (*pixels.get()).as_mut_slice() },
+        };
+
+        Ok(DrawHandle {
+            pixels,
+            depth: unsafe { &mut *window.depth.get() },
+            blend_mode: BlendMode::Opaque,
+            window,
+            frame: FrameInfo::default(),
+        })
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    fn lock_texture(&self, texture: *mut SDL_Texture) -> Result<&mut [u32], Error> {
         let mut ptr: *mut u32 = ptr::null_mut();
         let mut pitch = 0;
         let num_pixels = (self.width * self.height) as usize;
 
-        let pixels = unsafe {
+        unsafe {
             SDL_LockTexture(
-                self.texture,
+                texture,
                 ptr::null(),
                 ptr::addr_of_mut!(ptr).cast(),
                 &mut pitch,
-            );
+            )
+            .check_err(Error::Lock)?;
 
             debug_assert!(pitch / self.width as i32 == size_of::<u32>() as i32);
 
-            slice::from_raw_parts_mut(ptr, num_pixels)
-        };
-
-        DrawHandle {
-            pixels,
-            window: self,
+            Ok(slice::from_raw_parts_mut(ptr, num_pixels))
         }
     }
 
     fn present(&self) {
+        let Backend::Sdl {
+            renderer, texture, ..
+        } = self.backend
+        else {
+            return;
+        };
+
         unsafe {
-            SDL_UnlockTexture(self.texture);
-            SDL_RenderCopy(self.renderer, self.texture, ptr::null(), ptr::null());
-            SDL_RenderPresent(self.renderer);
+            SDL_UnlockTexture(texture);
+            SDL_RenderCopy(renderer, texture, ptr::null(), ptr::null());
+            SDL_RenderPresent(renderer);
         }
     }
 
+    #[allow(clippy::cast_possible_truncation)]
+    fn mouse_pos(&self) -> (i32, i32) {
+        let (x, y) = mouse_pos();
+        let Backend::Sdl { renderer, .. } = self.backend else {
+            return (x, y);
+        };
+
+        let (mut logical_x, mut logical_y) = (0., 0.);
+
+        unsafe { SDL_RenderWindowToLogical(renderer, x, y, &mut logical_x, &mut logical_y) };
+
+        (logical_x.floor() as i32, logical_y.floor() as i32)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn mouse_pos_rel(&self) -> (i32, i32) {
+        let (x, y) = mouse_pos_rel();
+        let Backend::Sdl { renderer, .. } = self.backend else {
+            return (x, y);
+        };
+
+        let (mut scale_x, mut scale_y) = (1., 1.);
+
+        unsafe { SDL_RenderGetScale(renderer, &mut scale_x, &mut scale_y) };
+
+        ((x as f32 / scale_x) as i32, (y as f32 / scale_y) as i32)
+    }
+
     fn key_pressed(&self, key: Key) -> bool {
-        *self.key_pressed.get(&key).unwrap_or(&false)
+        self.input.key_pressed(key)
+    }
+
+    fn scancode_pressed(&self, scancode: Scancode) -> bool {
+        self.input.scancode_pressed(scancode)
+    }
+
+    fn mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.input.mouse_button_pressed(button)
     }
 
     fn set_window_title(&self, title: &str) {
+        let Backend::Sdl { handle, .. } = self.backend else {
+            return;
+        };
+
         let cstr = CString::new(title).expect("Title contains null byte");
 
         unsafe {
-            SDL_SetWindowTitle(self.handle, cstr.as_ptr());
+            SDL_SetWindowTitle(handle, cstr.as_ptr());
         }
     }
 }
 
 impl Drop for Window {
     fn drop(&mut self) {
+        let Backend::Sdl {
+            handle,
+            renderer,
+            texture,
+        } = self.backend
+        else {
+            return;
+        };
+
         unsafe {
-            SDL_DestroyTexture(self.texture);
-            SDL_DestroyRenderer(self.renderer);
-            SDL_DestroyWindow(self.handle);
+            SDL_DestroyTexture(texture);
+            SDL_DestroyRenderer(renderer);
+            SDL_DestroyWindow(handle);
         }
     }
 }
 
 impl<'p> DrawHandle<'p> {
+    /// Clears the pixels and, if one has been used, the depth buffer.
     pub fn clear(&mut self) {
         self.pixels.fill(0);
+        self.depth.fill(f32::INFINITY);
     }
 
+    /// Writes a pixel using the current blend mode, which is [`BlendMode::Opaque`] unless changed
+    /// with [`DrawHandle::set_blend_mode`].
     pub fn set(&mut self, x: u32, y: u32, color: u32) {
         if x >= self.width() || y >= self.height() {
             return;
         }
 
         let idx = y * self.width() + x;
-        self.pixels[idx as usize] = color | 0xff_00_00_00;
+        self.blend_index(idx as usize, color);
+    }
+
+    pub fn set_blended(&mut self, x: u32, y: u32, color: u32, mode: BlendMode) {
+        let prev = self.blend_mode;
+
+        self.blend_mode = mode;
+        self.set(x, y, color);
+        self.blend_mode = prev;
+    }
+
+    /// Sets the blend mode used by `set` and everything drawn through it: shapes, text and blits.
+    /// Resets to [`BlendMode::Opaque`] every frame.
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.blend_mode = mode;
+    }
+
+    /// Timing of the frame being rendered, all zero for handles not created by the main loop.
+    pub fn frame(&self) -> FrameInfo {
+        self.frame
     }
 
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn blend_index(&mut self, idx: usize, color: u32) {
+        let dst = &mut self.pixels[idx];
+        *dst = self.blend_mode.apply(color, *dst);
+    }
+
+    fn blend_range(&mut self, range: impl SliceIndex<[u32], Output = [u32]>, color: u32) {
+        let mode = self.blend_mode;
+        let pixels = &mut self.pixels[range];
+
+        if mode == BlendMode::Opaque {
+            pixels.fill(color | 0xff_00_00_00);
+        } else {
+            for dst in pixels {
+                *dst = mode.apply(color, *dst);
+            }
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `x` and `y` must be within the bounds of the framebuffer.
     pub unsafe fn set_unchecked(&mut self, x: u32, y: u32, color: u32) {
         let idx = y * self.width() + x;
         self.set_unchecked_index(idx as usize, color);
@@ -396,11 +1294,11 @@ impl<'p> DrawHandle<'p> {
     }
 
     pub fn mouse_pos(&self) -> (i32, i32) {
-        mouse_pos()
+        self.window.mouse_pos()
     }
 
     pub fn mouse_pos_rel(&self) -> (i32, i32) {
-        mouse_pos_rel()
+        self.window.mouse_pos_rel()
     }
 
     pub fn as_slice(&mut self) -> &mut [u32] {
@@ -408,6 +1306,10 @@ impl<'p> DrawHandle<'p> {
     }
 
     pub fn pause(&mut self, unpause_key: Key) {
+        if self.window.is_headless() {
+            return;
+        }
+
         let grab = get_mouse_grab();
 
         set_mouse_grab(false);
@@ -426,38 +1328,42 @@ impl<'p> DrawHandle<'p> {
         self.window.key_pressed(key)
     }
 
+    pub fn scancode_pressed(&self, scancode: Scancode) -> bool {
+        self.window.scancode_pressed(scancode)
+    }
+
+    pub fn mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.window.mouse_button_pressed(button)
+    }
+
     pub fn draw_text(&mut self, pos_x: u32, pos_y: u32, color: u32, text: &str) {
         TEXT_RENDERER.render(self, pos_x, pos_y, color, text);
     }
 
-    pub fn render_window(&self, window: &Window, state: &mut impl MainLoop) {
-        let mut handle = window.start_render();
+    pub fn render_window(&self, window: &mut Window, state: &mut impl MainLoop) {
+        let mut handle = window.start_render().unwrap_or_else(|err| panic!("{err}"));
         state.render(&mut handle);
         window.present();
     }
 }
 
 impl CheckErr for int {
-    fn check_err(self, action: &'static str) -> Self {
+    fn check_err(self, err: fn(String) -> Error) -> Result<Self, Error> {
         if self == 0 {
-            return self;
+            return Ok(self);
         }
 
-        let err_str = unsafe { CStr::from_ptr(SDL_GetError()) };
-
-        panic!("Failed to {action}: {err_str:?}");
+        Err(err(sdl_error()))
     }
 }
 
 impl<T> CheckErr for *mut T {
-    fn check_err(self, action: &'static str) -> Self {
+    fn check_err(self, err: fn(String) -> Error) -> Result<Self, Error> {
         if !self.is_null() {
-            return self;
+            return Ok(self);
         }
 
-        let err_str = unsafe { CStr::from_ptr(SDL_GetError()) };
-
-        panic!("Failed to {action}: {err_str:?}");
+        Err(err(sdl_error()))
     }
 }
 
@@ -484,10 +1390,17 @@ impl FpsCounter {
     }
 }
 
-fn init_library() {
-    let flags = SDL_INIT_VIDEO | SDL_INIT_EVENTS | SDL_INIT_TIMER;
+fn init_library() -> Result<(), Error> {
+    let flags = SDL_INIT_VIDEO | SDL_INIT_EVENTS | SDL_INIT_TIMER | SDL_INIT_GAMECONTROLLER;
+
+    // Closing the last window goes through `Event::CloseRequested` instead.
+    unsafe { SDL_SetHint(c"SDL_QUIT_ON_LAST_WINDOW_CLOSE".as_ptr(), c"0".as_ptr()) };
+    unsafe { SDL_Init(flags) }.check_err(Error::Init)?;
+
+    // SDL starts with text input enabled, which pops up IMEs.
+    unsafe { SDL_StopTextInput() };
 
-    unsafe { SDL_Init(flags) }.check_err("initialize SDL");
+    Ok(())
 }
 
 fn create_window(
@@ -496,51 +1409,111 @@ fn create_window(
     w: int,
     h: int,
     title: &'static str,
-) -> *mut SDL_Window {
+    flags: u32,
+) -> Result<*mut SDL_Window, Error> {
     let cstr = CString::new(title).expect("Title contains null byte");
     let any_pos = SDL_WINDOWPOS_UNDEFINED_MASK as int;
     let x = x.unwrap_or(any_pos);
     let y = y.unwrap_or(any_pos);
-    let flags = 0;
 
-    unsafe { SDL_CreateWindow(cstr.as_ptr(), x, y, w, h, flags) }.check_err("create window")
+    unsafe { SDL_CreateWindow(cstr.as_ptr(), x, y, w, h, flags) }.check_err(Error::Window)
+}
+
+fn fullscreen_flags(mode: Fullscreen) -> u32 {
+    match mode {
+        Fullscreen::Off => 0,
+        Fullscreen::Exclusive => SDL_WindowFlags::SDL_WINDOW_FULLSCREEN as u32,
+        Fullscreen::Desktop => SDL_WindowFlags::SDL_WINDOW_FULLSCREEN_DESKTOP as u32,
+    }
 }
 
-fn create_renderer(window: *mut SDL_Window) -> *mut SDL_Renderer {
-    let flags = SDL_RendererFlags::SDL_RENDERER_ACCELERATED as u32;
+fn create_renderer(window: *mut SDL_Window, vsync: bool) -> Result<*mut SDL_Renderer, Error> {
+    let mut flags = SDL_RendererFlags::SDL_RENDERER_ACCELERATED as u32;
 
-    unsafe { SDL_CreateRenderer(window, -1, flags) }.check_err("create renderer")
+    if vsync {
+        flags |= SDL_RendererFlags::SDL_RENDERER_PRESENTVSYNC as u32;
+    }
+
+    unsafe { SDL_CreateRenderer(window, -1, flags) }.check_err(Error::Renderer)
 }
 
-fn create_texture(renderer: *mut SDL_Renderer, w: int, h: int) -> *mut SDL_Texture {
+fn create_texture(renderer: *mut SDL_Renderer, w: int, h: int) -> Result<*mut SDL_Texture, Error> {
     let format = SDL_PixelFormatEnum::SDL_PIXELFORMAT_ARGB8888 as u32;
     let access = SDL_TextureAccess::SDL_TEXTUREACCESS_STREAMING as int;
 
-    unsafe { SDL_CreateTexture(renderer, format, access, w, h) }.check_err("create texture")
+    let texture =
+        unsafe { SDL_CreateTexture(renderer, format, access, w, h) }.check_err(Error::Texture)?;
+
+    unsafe { SDL_SetTextureScaleMode(texture, SDL_ScaleMode::SDL_ScaleModeNearest) };
+
+    Ok(texture)
+}
+
+fn sdl_error() -> String {
+    let err_str = unsafe { CStr::from_ptr(SDL_GetError()) };
+
+    err_str.to_string_lossy().into_owned()
 }
 
 fn get_window_id(window: *mut SDL_Window) -> u32 {
     unsafe { SDL_GetWindowID(window) }
 }
 
-fn current_time_seconds() -> f64 {
-    let ms = unsafe { SDL_GetTicks() };
+// SDL may report scancodes missing from the bindings' enum.
+fn raw_scancode(keysym: &SDL_Keysym) -> Scancode {
+    let raw = unsafe { ptr::addr_of!(keysym.scancode).cast::<i32>().read() };
 
-    f64::from(ms) / 1000.0
+    Scancode::from_raw(raw)
 }
 
-fn limit_fps(target_fps: f64, real_time: f64) {
-    let frame_end = current_time_seconds();
-    let frame_time = frame_end - real_time;
-    let to_sleep_f = 1000.0 / target_fps - frame_time;
+#[allow(clippy::cast_precision_loss)]
+fn wheel_event(wheel: &SDL_MouseWheelEvent) -> Event {
+    // The precise deltas are zero on SDL versions before 2.0.18.
+    let (mut x, mut y) = if wheel.preciseX == 0. && wheel.preciseY == 0. {
+        (wheel.x as f32, wheel.y as f32)
+    } else {
+        (wheel.preciseX, wheel.preciseY)
+    };
 
-    if to_sleep_f.is_sign_negative() {
-        return;
+    if wheel.direction == SDL_MouseWheelDirection::SDL_MOUSEWHEEL_FLIPPED as u32 {
+        x = -x;
+        y = -y;
     }
 
-    let to_sleep = to_sleep_f.floor() as u32;
+    Event::MouseWheel { x, y }
+}
 
-    unsafe { SDL_Delay(to_sleep) };
+#[allow(clippy::cast_sign_loss)]
+fn window_event(window: &SDL_WindowEvent) -> Option<Event> {
+    use SDL_WindowEventID as Id;
+
+    let id = window.windowID;
+    let event = match window.event {
+        e if e == Id::SDL_WINDOWEVENT_SIZE_CHANGED as u8 => Event::Resized {
+            window: id,
+            width: window.data1.max(0) as u32,
+            height: window.data2.max(0) as u32,
+        },
+        e if e == Id::SDL_WINDOWEVENT_FOCUS_GAINED as u8 => Event::FocusGained(id),
+        e if e == Id::SDL_WINDOWEVENT_FOCUS_LOST as u8 => Event::FocusLost(id),
+        e if e == Id::SDL_WINDOWEVENT_MINIMIZED as u8 => Event::Minimized(id),
+        e if e == Id::SDL_WINDOWEVENT_RESTORED as u8 => Event::Restored(id),
+        e if e == Id::SDL_WINDOWEVENT_EXPOSED as u8 => Event::Exposed(id),
+        _ => return Option::None,
+    };
+
+    Some(event)
+}
+
+fn screenshot_file_name() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+
+    format!(
+        "screenshot-{millis}.{}",
+        ImageFormat::preferred().extension()
+    )
 }
 
 fn poll_key_pressed(key: Key) -> bool {
@@ -553,11 +1526,11 @@ fn poll_key_pressed(key: Key) -> bool {
             }
 
             let event = event_ptr.assume_init();
-            let type_ = std::mem::transmute::<u32, SDL_EventType>(event.type_);
+            let type_ = event.type_;
 
             match type_ {
-                SDL_EventType::SDL_KEYDOWN => {
-                    let event_key = std::mem::transmute::<i32, Key>(event.key.keysym.sym);
+                event_type::KEYDOWN => {
+                    let event_key = Key::from_raw(event.key.keysym.sym);
                     if event_key == key {
                         return true;
                     }
@@ -565,7 +1538,13 @@ fn poll_key_pressed(key: Key) -> bool {
                         std::process::exit(0);
                     }
                 }
-                SDL_EventType::SDL_QUIT => std::process::exit(0),
+                event_type::QUIT => std::process::exit(0),
+                // With SDL_QUIT_ON_LAST_WINDOW_CLOSE off, closing the window doesn't send QUIT.
+                event_type::WINDOWEVENT
+                    if event.window.event == SDL_WindowEventID::SDL_WINDOWEVENT_CLOSE as u8 =>
+                {
+                    std::process::exit(0)
+                }
                 _ => (),
             }
         }
@@ -613,3 +1592,69 @@ fn get_mouse_grab() -> bool {
 
     state == SDL_bool::SDL_TRUE
 }
+
+// Transmuting newer SDL event types into the bindings' enum would be undefined behaviour.
+mod event_type {
+    use sdl2_sys::SDL_EventType;
+
+    pub const CONTROLLERAXISMOTION: u32 = SDL_EventType::SDL_CONTROLLERAXISMOTION as u32;
+    pub const CONTROLLERBUTTONDOWN: u32 = SDL_EventType::SDL_CONTROLLERBUTTONDOWN as u32;
+    pub const CONTROLLERBUTTONUP: u32 = SDL_EventType::SDL_CONTROLLERBUTTONUP as u32;
+    pub const CONTROLLERDEVICEADDED: u32 = SDL_EventType::SDL_CONTROLLERDEVICEADDED as u32;
+    pub const CONTROLLERDEVICEREMOVED: u32 = SDL_EventType::SDL_CONTROLLERDEVICEREMOVED as u32;
+    pub const KEYDOWN: u32 = SDL_EventType::SDL_KEYDOWN as u32;
+    pub const KEYUP: u32 = SDL_EventType::SDL_KEYUP as u32;
+    pub const MOUSEBUTTONDOWN: u32 = SDL_EventType::SDL_MOUSEBUTTONDOWN as u32;
+    pub const MOUSEBUTTONUP: u32 = SDL_EventType::SDL_MOUSEBUTTONUP as u32;
+    pub const MOUSEMOTION: u32 = SDL_EventType::SDL_MOUSEMOTION as u32;
+    pub const MOUSEWHEEL: u32 = SDL_EventType::SDL_MOUSEWHEEL as u32;
+    pub const QUIT: u32 = SDL_EventType::SDL_QUIT as u32;
+    pub const TEXTEDITING: u32 = SDL_EventType::SDL_TEXTEDITING as u32;
+    pub const TEXTINPUT: u32 = SDL_EventType::SDL_TEXTINPUT as u32;
+    pub const WINDOWEVENT: u32 = SDL_EventType::SDL_WINDOWEVENT as u32;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    struct Blank;
+
+    impl MainLoop for Blank {
+        fn handle_event(&mut self, _fb: &mut Framebuffer, _event: &Event) {}
+
+        fn update(&mut self, _fb: &mut Framebuffer, _dt: f32, _time: f64) {}
+
+        fn render(&mut self, d: &mut DrawHandle) {
+            d.clear();
+        }
+    }
+
+    #[test]
+    fn screenshot_rejects_unknown_formats_up_front() {
+        let mut fb = Framebuffer::headless(4, 4, 60);
+
+        assert!(matches!(
+            fb.screenshot("frame.xyz"),
+            Err(Error::UnsupportedFormat(_))
+        ));
+        assert!(matches!(
+            fb.screenshot("frame"),
+            Err(Error::UnsupportedFormat(_))
+        ));
+        assert!(fb.screenshot("frame.ppm").is_ok());
+    }
+
+    #[test]
+    fn failed_screenshot_keeps_running() {
+        let mut fb = Framebuffer::headless(4, 4, 60);
+        let path = env::temp_dir().join("picofb-missing-dir").join("frame.ppm");
+
+        fb.screenshot(path).unwrap();
+        fb.run_frames(&mut Blank, 2);
+
+        assert!(fb.pixels().is_some());
+    }
+}
@@ -0,0 +1,25 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+    /// The first extra button, usually "back".
+    X1,
+    /// The second extra button, usually "forward".
+    X2,
+    Other(u8),
+}
+
+impl MouseButton {
+    /// Converts an SDL button index, where 1 is the left button.
+    pub fn from_raw(button: u8) -> Self {
+        match button {
+            1 => Self::Left,
+            2 => Self::Middle,
+            3 => Self::Right,
+            4 => Self::X1,
+            5 => Self::X2,
+            _ => Self::Other(button),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+/// Settings for [`crate::Framebuffer::with_options`] and [`crate::Window::with_options`]. The
+/// defaults match [`crate::Framebuffer::new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowOptions {
+    /// Position of the window's top-left corner, or `None` to let the OS choose.
+    pub x: Option<u32>,
+    pub y: Option<u32>,
+    /// Whether the user can resize the window.
+    pub resizable: bool,
+    /// What a resized window does with its pixels.
+    pub resize_policy: ResizePolicy,
+    /// Size of a buffer pixel in screen pixels, for pixel art. The window starts out `scale` times
+    /// the buffer size and the buffer is shown scaled by a whole number, letterboxed if needed.
+    /// Mouse events and positions are in buffer pixels. Values below 1 count as 1.
+    pub scale: u32,
+    /// Whether to start in fullscreen, and which kind of fullscreen
+    /// [`crate::Framebuffer::set_fullscreen`] switches to.
+    pub fullscreen: Fullscreen,
+    /// Hide the title bar and window frame.
+    pub borderless: bool,
+    /// Wait for the display's vertical blank when presenting, which avoids tearing and caps the
+    /// frame rate at the refresh rate.
+    pub vsync: bool,
+}
+
+impl Default for WindowOptions {
+    fn default() -> Self {
+        Self {
+            x: None,
+            y: None,
+            resizable: false,
+            resize_policy: ResizePolicy::default(),
+            scale: 1,
+            fullscreen: Fullscreen::default(),
+            borderless: false,
+            vsync: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResizePolicy {
+    /// Reallocate the pixel buffer to match the new window size, so one buffer pixel stays
+    /// `scale` screen pixels. The contents are lost, so redraw everything on the next frame.
+    #[default]
+    ResizeBuffer,
+    /// Keep the pixel buffer size and scale it to fit the window, adding black bars where the
+    /// aspect ratios differ. Mouse coordinates stay in buffer pixels.
+    FixedResolution,
+}
+
+/// The pixel buffer keeps its size in fullscreen and is scaled to fit the screen, letterboxed
+/// where the aspect ratios differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fullscreen {
+    #[default]
+    Off,
+    /// Switch the display to the video mode closest to the window size.
+    Exclusive,
+    /// Cover the screen at the desktop resolution, without a video mode change.
+    Desktop,
+}
+
+/// Cap on how often [`crate::Framebuffer::run`] renders, set with
+/// [`crate::Framebuffer::set_frame_limit`]. Applies on top of vsync.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameLimit {
+    /// At most this many frames per second. Values that aren't positive and finite don't limit.
+    Fps(f64),
+    Unlimited,
+}
+
+impl Default for FrameLimit {
+    fn default() -> Self {
+        Self::Fps(500.0)
+    }
+}
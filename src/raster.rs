@@ -0,0 +1,363 @@
+use crate::DrawHandle;
+
+/// Decides which regions of a self-intersecting polygon are inside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    #[default]
+    EvenOdd,
+    NonZero,
+}
+
+const SUBPIXEL_BITS: u32 = 4;
+const SUBPIXEL_ONE: i64 = 1 << SUBPIXEL_BITS;
+const SUBPIXEL_HALF: i64 = SUBPIXEL_ONE / 2;
+
+/// Vertices further away than this are clamped so edge functions can't overflow.
+const COORD_LIMIT: f32 = (1 << 24) as f32;
+
+impl DrawHandle<'_> {
+    /// Fills a triangle of either winding. Vertices have sub-pixel precision, pixels are covered
+    /// when their center is inside, and pixels exactly on a shared edge are drawn by only one of
+    /// the adjacent triangles (top-left rule).
+    pub fn fill_triangle(&mut self, p0: (f32, f32), p1: (f32, f32), p2: (f32, f32), color: u32) {
+        let (width, height) = (self.width(), self.height());
+
+        rasterize_triangle(width, height, [p0, p1, p2], |x, y, _| self.set(x, y, color));
+    }
+
+    /// Like [`DrawHandle::fill_triangle`], but calls `shader` for every covered pixel with its
+    /// coordinates and barycentric weights of `p0`, `p1` and `p2`, and draws the returned color.
+    pub fn fill_triangle_with(
+        &mut self,
+        p0: (f32, f32),
+        p1: (f32, f32),
+        p2: (f32, f32),
+        mut shader: impl FnMut(u32, u32, [f32; 3]) -> u32,
+    ) {
+        let (width, height) = (self.width(), self.height());
+
+        rasterize_triangle(width, height, [p0, p1, p2], |x, y, bary| {
+            let color = shader(x, y, bary);
+            self.set(x, y, color);
+        });
+    }
+
+    /// Fills an arbitrary, possibly concave or self-intersecting, polygon. The last point is
+    /// implicitly connected to the first.
+    pub fn fill_polygon(&mut self, points: &[(f32, f32)], rule: FillRule, color: u32) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let (min_y, max_y) = points
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), p| {
+                (lo.min(p.1), hi.max(p.1))
+            });
+
+        let Some((y0, y1)) = pixel_range(min_y, max_y, self.height()) else {
+            return;
+        };
+
+        let mut crossings: Vec<(f32, i32)> = Vec::with_capacity(points.len());
+
+        for y in y0..=y1 {
+            #[allow(clippy::cast_precision_loss)]
+            let center = y as f32 + 0.5;
+
+            crossings.clear();
+
+            for (i, &(ax, ay)) in points.iter().enumerate() {
+                let (bx, by) = points[(i + 1) % points.len()];
+
+                // Half-open in y, so a vertex shared by two edges is only counted once.
+                let winding = if ay <= center && center < by {
+                    1
+                } else if by <= center && center < ay {
+                    -1
+                } else {
+                    continue;
+                };
+
+                let x = ax + (center - ay) * (bx - ax) / (by - ay);
+                crossings.push((x, winding));
+            }
+
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+
+            for pair in crossings.windows(2) {
+                let (xa, dir) = pair[0];
+                let xb = pair[1].0;
+
+                winding += dir;
+
+                let inside = match rule {
+                    FillRule::EvenOdd => winding % 2 != 0,
+                    FillRule::NonZero => winding != 0,
+                };
+
+                if inside {
+                    self.fill_span(xa, xb, y, color);
+                }
+            }
+        }
+    }
+
+    /// Fills the pixels of row `y` whose centers lie in `[xa, xb)`.
+    fn fill_span(&mut self, xa: f32, xb: f32, y: u32, color: u32) {
+        let first = (xa - 0.5).ceil();
+        let last = (xb - 0.5).ceil() - 1.;
+
+        if last < first || last < 0. {
+            return;
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let (first, last) = (first.max(-1.) as i32, last.min(i32::MAX as f32) as i32);
+
+        #[allow(clippy::cast_possible_wrap)]
+        self.draw_hline(first, last, y as i32, color);
+    }
+}
+
+/// Calls `f` with the coordinates and barycentric weights of every pixel inside the triangle
+/// whose center is within the `width` x `height` area.
+pub(crate) fn rasterize_triangle(
+    width: u32,
+    height: u32,
+    vertices: [(f32, f32); 3],
+    mut f: impl FnMut(u32, u32, [f32; 3]),
+) {
+    let [v0, v1, v2] = vertices.map(to_fixed);
+
+    let area = Edge::new(v0, v1).eval(v2.0, v2.1);
+    if area == 0 {
+        return;
+    }
+
+    let sign = area.signum();
+    let edges = [
+        Edge::new(v1, v2).oriented(sign),
+        Edge::new(v2, v0).oriented(sign),
+        Edge::new(v0, v1).oriented(sign),
+    ];
+    #[allow(clippy::cast_precision_loss)]
+    let inv_area = 1. / (area * sign) as f32;
+
+    let min_x = v0.0.min(v1.0).min(v2.0);
+    let max_x = v0.0.max(v1.0).max(v2.0);
+    let min_y = v0.1.min(v1.1).min(v2.1);
+    let max_y = v0.1.max(v1.1).max(v2.1);
+
+    let Some((x0, x1)) = fixed_pixel_range(min_x, max_x, width) else {
+        return;
+    };
+    let Some((y0, y1)) = fixed_pixel_range(min_y, max_y, height) else {
+        return;
+    };
+
+    let sample_x0 = pixel_center(x0);
+
+    for y in y0..=y1 {
+        let sample_y = pixel_center(y);
+        let mut w = edges.map(|e| e.eval(sample_x0, sample_y));
+
+        for x in x0..=x1 {
+            if edges.iter().zip(w).all(|(e, w)| e.covers(w)) {
+                #[allow(clippy::cast_precision_loss)]
+                f(x, y, w.map(|w| w as f32 * inv_area));
+            }
+
+            for (w, e) in w.iter_mut().zip(&edges) {
+                *w += e.step_x;
+            }
+        }
+    }
+}
+
+/// Edge function `a * x + b * y + c`, positive on the inside of the triangle.
+#[derive(Clone, Copy)]
+struct Edge {
+    a: i64,
+    b: i64,
+    c: i64,
+    step_x: i64,
+    top_left: bool,
+}
+
+impl Edge {
+    fn new(from: (i64, i64), to: (i64, i64)) -> Self {
+        let a = from.1 - to.1;
+        let b = to.0 - from.0;
+        let c = -(a * from.0 + b * from.1);
+
+        Self {
+            a,
+            b,
+            c,
+            step_x: a * SUBPIXEL_ONE,
+            top_left: false,
+        }
+    }
+
+    fn oriented(self, sign: i64) -> Self {
+        let (a, b) = (self.a * sign, self.b * sign);
+
+        Self {
+            a,
+            b,
+            c: self.c * sign,
+            step_x: self.step_x * sign,
+            // With y pointing down and positive area, left edges go up and top edges go right.
+            top_left: a > 0 || (a == 0 && b > 0),
+        }
+    }
+
+    fn eval(&self, x: i64, y: i64) -> i64 {
+        self.a * x + self.b * y + self.c
+    }
+
+    fn covers(&self, w: i64) -> bool {
+        w > 0 || (w == 0 && self.top_left)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn to_fixed((x, y): (f32, f32)) -> (i64, i64) {
+    let scale = SUBPIXEL_ONE as f32;
+    let fix = |v: f32| (v.clamp(-COORD_LIMIT, COORD_LIMIT) * scale).round() as i64;
+
+    (fix(x), fix(y))
+}
+
+fn pixel_center(p: u32) -> i64 {
+    i64::from(p) * SUBPIXEL_ONE + SUBPIXEL_HALF
+}
+
+/// Range of pixels whose centers lie within `[min, max]` given in fixed point, clipped to `len`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn fixed_pixel_range(min: i64, max: i64, len: u32) -> Option<(u32, u32)> {
+    let first = (min - SUBPIXEL_HALF + SUBPIXEL_ONE - 1).div_euclid(SUBPIXEL_ONE);
+    let last = (max - SUBPIXEL_HALF).div_euclid(SUBPIXEL_ONE);
+    let (first, last) = (first.max(0), last.min(i64::from(len) - 1));
+
+    (first <= last).then_some((first as u32, last as u32))
+}
+
+/// Range of pixel rows whose centers lie within `[min, max]`, clipped to `len`.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn pixel_range(min: f32, max: f32, len: u32) -> Option<(u32, u32)> {
+    let first = (min - 0.5).ceil().max(0.);
+    let last = (max - 0.5).floor().min(len as f32 - 1.);
+
+    (first <= last).then_some((first as u32, last as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: u32 = 32;
+
+    fn coverage(triangles: &[[(f32, f32); 3]]) -> Vec<u32> {
+        let mut counts = vec![0; (SIZE * SIZE) as usize];
+
+        for &triangle in triangles {
+            rasterize_triangle(SIZE, SIZE, triangle, |x, y, _| {
+                counts[(y * SIZE + x) as usize] += 1;
+            });
+        }
+
+        counts
+    }
+
+    #[test]
+    fn shared_edge_is_drawn_once() {
+        let (a, b, c, d) = ((1.3, 1.), (20.5, 4.25), (6., 25.7), (27., 29.));
+        let counts = coverage(&[[a, b, c], [b, d, c]]);
+
+        assert!(counts.iter().all(|&n| n <= 1));
+
+        // No gaps along the shared edge either.
+        for i in 1..10 {
+            let t = i as f32 / 10.;
+            let x = (b.0 + (c.0 - b.0) * t) as u32;
+            let y = (b.1 + (c.1 - b.1) * t) as u32;
+
+            assert_eq!(counts[(y * SIZE + x) as usize], 1, "({x}, {y})");
+        }
+    }
+
+    #[test]
+    fn split_quad_covers_every_pixel_once() {
+        let (a, b, c, d) = ((2., 2.), (10., 2.), (10., 10.), (2., 10.));
+
+        for triangles in [[[a, b, c], [a, c, d]], [[a, b, d], [b, c, d]]] {
+            let counts = coverage(&triangles);
+
+            for y in 0..SIZE {
+                for x in 0..SIZE {
+                    let inside = (2..10).contains(&x) && (2..10).contains(&y);
+                    assert_eq!(
+                        counts[(y * SIZE + x) as usize],
+                        u32::from(inside),
+                        "({x}, {y})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fan_covers_every_pixel_at_most_once() {
+        let center = (16., 16.);
+        let rim: Vec<(f32, f32)> = (0..7)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::TAU / 7.;
+                (16. + 12. * angle.cos(), 16. + 12. * angle.sin())
+            })
+            .collect();
+        let triangles: Vec<_> = (0..7).map(|i| [center, rim[i], rim[(i + 1) % 7]]).collect();
+        let counts = coverage(&triangles);
+
+        assert!(counts.iter().all(|&n| n <= 1));
+        assert_eq!(counts[(16 * SIZE + 16) as usize], 1);
+    }
+
+    #[test]
+    fn barycentric_weights_sum_to_one() {
+        let mut pixels = 0;
+
+        for vertices in [
+            [(1.5, 2.), (29., 5.5), (10., 30.)],
+            [(10., 30.), (29., 5.5), (1.5, 2.)],
+        ] {
+            rasterize_triangle(SIZE, SIZE, vertices, |_, _, bary| {
+                assert!((bary.iter().sum::<f32>() - 1.).abs() < 1e-5, "{bary:?}");
+                assert!(bary.iter().all(|w| (0. ..=1.).contains(w)), "{bary:?}");
+                pixels += 1;
+            });
+        }
+
+        assert!(pixels > 0);
+    }
+
+    #[test]
+    fn barycentric_weights_interpolate_vertices() {
+        let vertices = [(0., 0.), (32., 0.), (0., 32.)];
+
+        rasterize_triangle(SIZE, SIZE, vertices, |x, y, bary| {
+            let px = bary[1] * 32.;
+            let py = bary[2] * 32.;
+
+            assert!((px - (x as f32 + 0.5)).abs() < 1e-3);
+            assert!((py - (y as f32 + 0.5)).abs() < 1e-3);
+        });
+    }
+}
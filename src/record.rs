@@ -0,0 +1,315 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{codec, Error, Framebuffer, ImageFormat};
+
+/// Output of [`Framebuffer::start_recording`]. Frames are written at the update rate: a frame
+/// that took several updates to reach is repeated, one that took none is dropped, so playback
+/// runs at the speed of the simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// Uncompressed YUV 4:4:4 stream, readable by ffmpeg and most video players.
+    Y4m,
+    /// A directory of numbered PNG files. Requires the `png` feature.
+    PngSequence,
+    /// A looping animated GIF with a palette quantized per frame. Requires the `gif` feature.
+    Gif,
+}
+
+pub(crate) struct Recorder {
+    width: u32,
+    height: u32,
+    sink: Sink,
+}
+
+enum Sink {
+    Y4m(BufWriter<File>),
+    PngSequence {
+        dir: PathBuf,
+        index: usize,
+    },
+    #[cfg(feature = "gif")]
+    Gif {
+        encoder: gif::Encoder<BufWriter<File>>,
+        update_rate: u64,
+        ticks: u64,
+        centis: u64,
+    },
+}
+
+impl Recorder {
+    fn create(
+        path: &Path,
+        format: RecordFormat,
+        width: u32,
+        height: u32,
+        update_rate: u32,
+    ) -> Result<Self, Error> {
+        let sink = match format {
+            RecordFormat::Y4m => {
+                let mut out = BufWriter::new(File::create(path)?);
+                writeln!(
+                    out,
+                    "YUV4MPEG2 W{width} H{height} F{update_rate}:1 Ip A1:1 C444"
+                )?;
+
+                Sink::Y4m(out)
+            }
+            RecordFormat::PngSequence => {
+                if !ImageFormat::Png.is_enabled() {
+                    return Err(Error::UnsupportedFormat(
+                        "png (enable the \"png\" feature)".to_owned(),
+                    ));
+                }
+
+                fs::create_dir_all(path)?;
+
+                Sink::PngSequence {
+                    dir: path.to_owned(),
+                    index: 0,
+                }
+            }
+            #[cfg(feature = "gif")]
+            RecordFormat::Gif => {
+                let too_large = || Error::Encode(format!("{width}x{height} is too large for GIF"));
+                let w = u16::try_from(width).map_err(|_| too_large())?;
+                let h = u16::try_from(height).map_err(|_| too_large())?;
+
+                let out = BufWriter::new(File::create(path)?);
+                let mut encoder = gif::Encoder::new(out, w, h, &[]).map_err(gif_error)?;
+                encoder
+                    .set_repeat(gif::Repeat::Infinite)
+                    .map_err(gif_error)?;
+
+                Sink::Gif {
+                    encoder,
+                    update_rate: u64::from(update_rate),
+                    ticks: 0,
+                    centis: 0,
+                }
+            }
+            #[cfg(not(feature = "gif"))]
+            RecordFormat::Gif => {
+                return Err(Error::UnsupportedFormat(
+                    "gif (enable the \"gif\" feature)".to_owned(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            width,
+            height,
+            sink,
+        })
+    }
+
+    /// Records a frame that was reached after `ticks` update steps.
+    pub(crate) fn write_frame(&mut self, pixels: &[u32], ticks: u32) -> Result<(), Error> {
+        if ticks == 0 {
+            return Ok(());
+        }
+
+        match &mut self.sink {
+            Sink::Y4m(out) => {
+                let planes = yuv444(pixels);
+
+                for _ in 0..ticks {
+                    out.write_all(b"FRAME\n")?;
+                    out.write_all(&planes)?;
+                }
+            }
+            Sink::PngSequence { dir, index } => {
+                let opaque: Vec<u32> = pixels.iter().map(|p| p | 0xff_00_00_00).collect();
+                let bytes = codec::encode(ImageFormat::Png, self.width, self.height, &opaque)?;
+
+                for _ in 0..ticks {
+                    fs::write(dir.join(format!("{index:05}.png")), &bytes)?;
+                    *index += 1;
+                }
+            }
+            #[cfg(feature = "gif")]
+            Sink::Gif {
+                encoder,
+                update_rate,
+                ticks: total,
+                centis,
+            } => {
+                // GIF delays are in hundredths of a second, so round the end time of each frame
+                // rather than its duration to keep the rounding error from accumulating.
+                *total += u64::from(ticks);
+                let end = (*total * 100 + *update_rate / 2) / *update_rate;
+                let delay = end - *centis;
+
+                if delay == 0 {
+                    return Ok(());
+                }
+
+                *centis = end;
+
+                let mut rgba: Vec<u8> = pixels
+                    .iter()
+                    .flat_map(|&pixel| {
+                        let [_, r, g, b] = pixel.to_be_bytes();
+                        [r, g, b, 0xff]
+                    })
+                    .collect();
+
+                #[allow(clippy::cast_possible_truncation)]
+                let mut frame = gif::Frame::from_rgba_speed(
+                    self.width as u16,
+                    self.height as u16,
+                    &mut rgba,
+                    10,
+                );
+                frame.delay = u16::try_from(delay).unwrap_or(u16::MAX);
+
+                encoder.write_frame(&frame).map_err(gif_error)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), Error> {
+        match self.sink {
+            Sink::Y4m(mut out) => out.flush()?,
+            Sink::PngSequence { .. } => (),
+            #[cfg(feature = "gif")]
+            Sink::Gif { encoder, .. } => {
+                encoder.into_inner()?.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Framebuffer {
+    /// Starts recording every rendered frame to `path`, which is a directory for
+    /// [`RecordFormat::PngSequence`]. A recording that is already running is finished first, as is
+    /// this one when the window's pixel buffer is resized. If writing a frame fails later, the
+    /// error is printed to stderr and the recording stops, but the run goes on.
+    pub fn start_recording(
+        &mut self,
+        path: impl AsRef<Path>,
+        format: RecordFormat,
+    ) -> Result<(), Error> {
+        self.stop_recording()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let update_rate = (1. / self.dt).round() as u32;
+
+        self.recorder = Some(Recorder::create(
+            path.as_ref(),
+            format,
+            self.width(),
+            self.height(),
+            update_rate,
+        )?);
+
+        Ok(())
+    }
+
+    /// Finishes the current recording, if any. Recordings still running when the framebuffer is
+    /// dropped are finished as well, ignoring errors.
+    pub fn stop_recording(&mut self) -> Result<(), Error> {
+        match self.recorder.take() {
+            Some(recorder) => recorder.finish(),
+            Option::None => Ok(()),
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+}
+
+/// Converts ARGB pixels to planar limited-range BT.601 YCbCr, as Y4M players expect by default.
+fn yuv444(pixels: &[u32]) -> Vec<u8> {
+    let len = pixels.len();
+    let mut planes = vec![0; len * 3];
+
+    for (i, &pixel) in pixels.iter().enumerate() {
+        let [_, r, g, b] = pixel.to_be_bytes().map(i32::from);
+
+        let y = ((66 * r + 129 * g + 25 * b + 128) >> 8) + 16;
+        let cb = ((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128;
+        let cr = ((112 * r - 94 * g - 18 * b + 128) >> 8) + 128;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        {
+            planes[i] = y as u8;
+            planes[len + i] = cb as u8;
+            planes[2 * len + i] = cr as u8;
+        }
+    }
+
+    planes
+}
+
+#[cfg(feature = "gif")]
+fn gif_error(err: gif::EncodingError) -> Error {
+    match err {
+        gif::EncodingError::Io(err) => err.into(),
+        gif::EncodingError::Format(err) => Error::Encode(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::{DrawHandle, Event, MainLoop};
+
+    struct Blank;
+
+    impl MainLoop for Blank {
+        fn handle_event(&mut self, _fb: &mut Framebuffer, _event: &Event) {}
+
+        fn update(&mut self, _fb: &mut Framebuffer, _dt: f32, _time: f64) {}
+
+        fn render(&mut self, d: &mut DrawHandle) {
+            d.clear();
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("picofb-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        let _ = fs::remove_file(&path);
+
+        path
+    }
+
+    #[test]
+    fn y4m_has_one_frame_per_update() {
+        let path = temp_path("y4m");
+        let mut fb = Framebuffer::headless(4, 2, 30);
+
+        fb.start_recording(&path, RecordFormat::Y4m).unwrap();
+        fb.run_frames(&mut Blank, 3);
+        fb.stop_recording().unwrap();
+
+        let bytes = fs::read(&path).unwrap();
+        let header = b"YUV4MPEG2 W4 H2 F30:1 Ip A1:1 C444\n".len();
+        let frame = b"FRAME\n".len() + 4 * 2 * 3;
+
+        assert_eq!(bytes.len(), header + 3 * frame);
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn write_errors_stop_the_recording_not_the_run() {
+        let dir = temp_path("sequence");
+        let mut fb = Framebuffer::headless(4, 2, 30);
+
+        fb.start_recording(&dir, RecordFormat::PngSequence).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        fb.run_frames(&mut Blank, 2);
+
+        assert!(!fb.is_recording());
+    }
+}
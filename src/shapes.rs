@@ -0,0 +1,405 @@
+use crate::DrawHandle;
+
+impl DrawHandle<'_> {
+    /// Draws a horizontal line between `x0` and `x1`, both inclusive.
+    pub fn draw_hline(&mut self, x0: i32, x1: i32, y: i32, color: u32) {
+        let Some(y) = clip_coord(i64::from(y), self.height()) else {
+            return;
+        };
+        let Some((x0, x1)) = clip_span(x0, x1, self.width()) else {
+            return;
+        };
+
+        let row = (y * self.width()) as usize;
+        self.blend_range(row + x0 as usize..=row + x1 as usize, color);
+    }
+
+    /// Draws a vertical line between `y0` and `y1`, both inclusive.
+    pub fn draw_vline(&mut self, x: i32, y0: i32, y1: i32, color: u32) {
+        let Some(x) = clip_coord(i64::from(x), self.width()) else {
+            return;
+        };
+        let Some((y0, y1)) = clip_span(y0, y1, self.height()) else {
+            return;
+        };
+
+        let stride = self.width() as usize;
+        let start = (y0 * self.width() + x) as usize;
+        let end = (y1 * self.width() + x) as usize;
+
+        for idx in (start..=end).step_by(stride) {
+            self.blend_index(idx, color);
+        }
+    }
+
+    /// Draws a line between two points, both inclusive. Positions are computed per step so that
+    /// parts of the line outside the framebuffer are skipped rather than walked.
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: u32) {
+        if y0 == y1 {
+            self.draw_hline(x0, x1, y0, color);
+            return;
+        }
+
+        if x0 == x1 {
+            self.draw_vline(x0, y0, y1, color);
+            return;
+        }
+
+        let (x0, y0) = (i64::from(x0), i64::from(y0));
+        let (dx, dy) = (i64::from(x1) - x0, i64::from(y1) - y0);
+        let steps = dx.abs().max(dy.abs());
+
+        let (major_start, major_dir, major_len) = if dx.abs() >= dy.abs() {
+            (x0, dx.signum(), i64::from(self.width()))
+        } else {
+            (y0, dy.signum(), i64::from(self.height()))
+        };
+
+        let (t_min, t_max) = if major_dir > 0 {
+            (-major_start, major_len - 1 - major_start)
+        } else {
+            (major_start - (major_len - 1), major_start)
+        };
+
+        for t in t_min.max(0)..=t_max.min(steps) {
+            let x = x0 + div_round(i128::from(dx) * i128::from(t), steps);
+            let y = y0 + div_round(i128::from(dy) * i128::from(t), steps);
+
+            self.set_clipped(x, y, color);
+        }
+    }
+
+    pub fn draw_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let x1 = x.saturating_add_unsigned(width - 1);
+        let y1 = y.saturating_add_unsigned(height - 1);
+
+        // Each pixel is drawn once, so blended outlines don't get darker corners.
+        self.draw_hline(x, x1, y, color);
+
+        if y1 != y {
+            self.draw_hline(x, x1, y1, color);
+        }
+
+        if height > 2 {
+            self.draw_vline(x, y + 1, y1 - 1, color);
+
+            if x1 != x {
+                self.draw_vline(x1, y + 1, y1 - 1, color);
+            }
+        }
+    }
+
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let x1 = x.saturating_add_unsigned(width - 1);
+        let y1 = y.saturating_add_unsigned(height - 1);
+
+        let Some((y0, y1)) = clip_span(y, y1, self.height()) else {
+            return;
+        };
+
+        for row in y0..=y1 {
+            self.draw_hline(x, x1, row as i32, color);
+        }
+    }
+
+    /// Draws the outline of a circle using the midpoint circle algorithm.
+    pub fn draw_circle(&mut self, cx: i32, cy: i32, radius: u32, color: u32) {
+        let (cx, cy) = (i64::from(cx), i64::from(cy));
+        let mut x = i64::from(radius);
+        let mut y = 0;
+        let mut err = 1 - x;
+
+        while x >= y {
+            self.plot_mirrored(cx, cy, x, y, color);
+
+            if x != y {
+                self.plot_mirrored(cx, cy, y, x, color);
+            }
+
+            y += 1;
+
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Fills every pixel whose distance to the center is at most `radius`.
+    pub fn fill_circle(&mut self, cx: i32, cy: i32, radius: u32, color: u32) {
+        let r = radius.min(i32::MAX as u32) as i32;
+        let Some((y0, y1)) = clip_span(cy.saturating_sub(r), cy.saturating_add(r), self.height())
+        else {
+            return;
+        };
+
+        for y in y0..=y1 {
+            let dy = i64::from(y) - i64::from(cy);
+            let half = isqrt(i64::from(r) * i64::from(r) - dy * dy) as i32;
+
+            self.draw_hline(
+                cx.saturating_sub(half),
+                cx.saturating_add(half),
+                y as i32,
+                color,
+            );
+        }
+    }
+
+    /// Draws the outline of an axis-aligned ellipse using the midpoint ellipse algorithm.
+    pub fn draw_ellipse(&mut self, cx: i32, cy: i32, rx: u32, ry: u32, color: u32) {
+        let (cx, cy) = (i64::from(cx), i64::from(cy));
+        let (rx, ry) = (i64::from(rx), i64::from(ry));
+
+        // The midpoint algorithm leaves flat ellipses mostly undrawn.
+        if ry == 0 {
+            self.draw_hline(saturate(cx - rx), saturate(cx + rx), saturate(cy), color);
+            return;
+        }
+
+        if rx == 0 {
+            self.draw_vline(saturate(cx), saturate(cy - ry), saturate(cy + ry), color);
+            return;
+        }
+        let (rx2, ry2) = (rx * rx, ry * ry);
+
+        // Region 1: slope is shallower than -1, step in x.
+        let mut x = 0;
+        let mut y = ry;
+        let mut d = 4 * ry2 - 4 * rx2 * ry + rx2;
+
+        while ry2 * x <= rx2 * y {
+            self.plot_mirrored(cx, cy, x, y, color);
+
+            if d >= 0 {
+                y -= 1;
+                d -= 8 * rx2 * y;
+            }
+
+            x += 1;
+            d += 4 * ry2 * (2 * x + 1);
+        }
+
+        // Region 2: slope is steeper than -1, step in y.
+        let mut x = rx;
+        let mut y = 0;
+        let mut d = 4 * rx2 - 4 * ry2 * rx + ry2;
+
+        while rx2 * y < ry2 * x {
+            self.plot_mirrored(cx, cy, x, y, color);
+
+            if d >= 0 {
+                x -= 1;
+                d -= 8 * ry2 * x;
+            }
+
+            y += 1;
+            d += 4 * rx2 * (2 * y + 1);
+        }
+    }
+
+    /// Plots `(x, y)` mirrored into all four quadrants around the center, once per pixel.
+    fn plot_mirrored(&mut self, cx: i64, cy: i64, x: i64, y: i64, color: u32) {
+        self.set_clipped(cx + x, cy + y, color);
+
+        if x != 0 {
+            self.set_clipped(cx - x, cy + y, color);
+        }
+
+        if y != 0 {
+            self.set_clipped(cx + x, cy - y, color);
+        }
+
+        if x != 0 && y != 0 {
+            self.set_clipped(cx - x, cy - y, color);
+        }
+    }
+
+    fn set_clipped(&mut self, x: i64, y: i64, color: u32) {
+        if let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y)) {
+            self.set(x, y, color);
+        }
+    }
+}
+
+fn clip_coord(v: i64, len: u32) -> Option<u32> {
+    u32::try_from(v).ok().filter(|&v| v < len)
+}
+
+/// Orders and clamps an inclusive span to `0..len`, or returns `None` if it lies outside.
+fn clip_span(a: i32, b: i32, len: u32) -> Option<(u32, u32)> {
+    let (lo, hi) = (i64::from(a.min(b)), i64::from(a.max(b)));
+
+    if hi < 0 || lo >= i64::from(len) {
+        return None;
+    }
+
+    Some((lo.max(0) as u32, hi.min(i64::from(len) - 1) as u32))
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn saturate(v: i64) -> i32 {
+    v.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32
+}
+
+/// Divides rounding half away from zero. `d` must be positive.
+#[allow(clippy::cast_possible_truncation)]
+fn div_round(n: i128, d: i64) -> i64 {
+    let d = i128::from(d);
+    let q = if n >= 0 {
+        (2 * n + d) / (2 * d)
+    } else {
+        -((-2 * n + d) / (2 * d))
+    };
+
+    q as i64
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn isqrt(n: i64) -> i64 {
+    let mut root = (n as f64).sqrt() as i64;
+
+    while root * root > n {
+        root -= 1;
+    }
+
+    while (root + 1) * (root + 1) <= n {
+        root += 1;
+    }
+
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BlendMode, Window};
+
+    fn lit(window: &Window) -> Vec<(u32, u32)> {
+        let width = window.width();
+
+        window
+            .pixels()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .filter(|(_, &p)| p != 0)
+            .map(|(i, _)| (i as u32 % width, i as u32 / width))
+            .collect()
+    }
+
+    #[test]
+    fn flat_ellipse_is_a_horizontal_line() {
+        let mut window = Window::headless(16, 16);
+        window
+            .start_render()
+            .unwrap()
+            .draw_ellipse(8, 4, 5, 0, 0xff_ff_ff_ff);
+
+        let expected: Vec<_> = (3..=13).map(|x| (x, 4)).collect();
+        assert_eq!(lit(&window), expected);
+    }
+
+    #[test]
+    fn thin_ellipse_is_a_vertical_line() {
+        let mut window = Window::headless(16, 16);
+        window
+            .start_render()
+            .unwrap()
+            .draw_ellipse(4, 8, 0, 5, 0xff_ff_ff_ff);
+
+        let expected: Vec<_> = (3..=13).map(|y| (4, y)).collect();
+        assert_eq!(lit(&window), expected);
+    }
+
+    #[test]
+    fn point_ellipse_is_one_pixel() {
+        let mut window = Window::headless(4, 4);
+        window
+            .start_render()
+            .unwrap()
+            .draw_ellipse(1, 2, 0, 0, 0xff_ff_ff_ff);
+
+        assert_eq!(lit(&window), [(1, 2)]);
+    }
+
+    #[test]
+    fn ellipse_touches_its_extremes() {
+        let mut window = Window::headless(32, 32);
+        window
+            .start_render()
+            .unwrap()
+            .draw_ellipse(16, 16, 9, 4, 0xff_ff_ff_ff);
+
+        let pixels = lit(&window);
+        for point in [(7, 16), (25, 16), (16, 12), (16, 20)] {
+            assert!(pixels.contains(&point), "{point:?} missing");
+        }
+        assert!(pixels
+            .iter()
+            .all(|&(x, y)| (7..=25).contains(&x) && (12..=20).contains(&y)));
+    }
+
+    fn assert_blended_once(draw: impl FnOnce(&mut crate::DrawHandle)) {
+        let mut window = Window::headless(32, 32);
+        let mut d = window.start_render().unwrap();
+        d.as_slice().fill(0xff_00_00_00);
+        d.set_blend_mode(BlendMode::Alpha);
+        draw(&mut d);
+
+        let once = BlendMode::Alpha.apply(0x80_ff_ff_ff, 0xff_00_00_00);
+        let pixels = window.pixels().unwrap();
+
+        assert!(pixels.contains(&once));
+        assert!(pixels.iter().all(|&p| p == once || p == 0xff_00_00_00));
+    }
+
+    #[test]
+    fn blended_shapes_cover_each_pixel_once() {
+        let color = 0x80_ff_ff_ff;
+
+        assert_blended_once(|d| d.draw_rect(2, 3, 10, 6, color));
+        assert_blended_once(|d| d.draw_rect(2, 3, 5, 1, color));
+        assert_blended_once(|d| d.draw_rect(2, 3, 1, 5, color));
+        assert_blended_once(|d| d.draw_rect(2, 3, 1, 1, color));
+        assert_blended_once(|d| d.draw_rect(2, 3, 2, 2, color));
+        assert_blended_once(|d| d.fill_rect(2, 3, 10, 6, color));
+        assert_blended_once(|d| d.draw_line(1, 2, 29, 17, color));
+        assert_blended_once(|d| d.draw_circle(16, 16, 9, color));
+        assert_blended_once(|d| d.fill_circle(16, 16, 9, color));
+        assert_blended_once(|d| d.draw_ellipse(16, 16, 13, 5, color));
+        assert_blended_once(|d| d.draw_ellipse(16, 16, 4, 12, color));
+    }
+
+    #[test]
+    fn rect_outline_pixels() {
+        let mut window = Window::headless(8, 8);
+        window
+            .start_render()
+            .unwrap()
+            .draw_rect(1, 2, 4, 3, 0xff_ff_ff_ff);
+
+        let expected = [
+            (1, 2),
+            (2, 2),
+            (3, 2),
+            (4, 2),
+            (1, 3),
+            (4, 3),
+            (1, 4),
+            (2, 4),
+            (3, 4),
+            (4, 4),
+        ];
+        assert_eq!(lit(&window), expected);
+    }
+}
@@ -0,0 +1,263 @@
+//! Golden-image testing for [`MainLoop`] implementations.
+//!
+//! A state is rendered headlessly for a fixed number of frames and the final frame is compared
+//! against a reference PPM image. Set `PICOFB_BLESS=1` to write the current output as the new
+//! reference instead of comparing.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::{codec, Framebuffer, Image, MainLoop};
+
+pub const BLESS_VAR: &str = "PICOFB_BLESS";
+
+pub struct Snapshot {
+    pub width: u32,
+    pub height: u32,
+    pub frames: usize,
+    pub update_rate: i16,
+    /// Maximum allowed difference per color channel.
+    pub tolerance: u8,
+}
+
+impl Snapshot {
+    pub fn new(width: u32, height: u32, frames: usize) -> Self {
+        Self {
+            width,
+            height,
+            frames,
+            update_rate: 60,
+            tolerance: 0,
+        }
+    }
+
+    pub fn render(&self, state: &mut impl MainLoop) -> Vec<u32> {
+        let mut fb = Framebuffer::headless(self.width, self.height, self.update_rate);
+
+        fb.run_frames(state, self.frames);
+
+        fb.pixels().map(<[u32]>::to_vec).unwrap_or_default()
+    }
+
+    /// Renders `state` and compares the result against the reference image at `path`.
+    ///
+    /// On mismatch the actual frame and a diff image, with differing pixels highlighted in red,
+    /// are written next to the reference before panicking.
+    pub fn assert(&self, state: &mut impl MainLoop, path: impl AsRef<Path>) {
+        self.check(state, path.as_ref(), bless_requested());
+    }
+
+    fn check(&self, state: &mut impl MainLoop, path: &Path, bless: bool) {
+        let actual = self.render(state);
+
+        if bless {
+            codec::save(path, self.width, self.height, &actual)
+                .unwrap_or_else(|err| panic!("Failed to write snapshot {path:?}: {err}"));
+            return;
+        }
+
+        let expected = Image::load(path).unwrap_or_else(|err| {
+            panic!("Failed to read snapshot {path:?}: {err} (run with {BLESS_VAR}=1 to create it)")
+        });
+
+        if (expected.width(), expected.height()) != (self.width, self.height) {
+            panic!(
+                "Snapshot {path:?} is {}x{}, rendered frame is {}x{}",
+                expected.width(),
+                expected.height(),
+                self.width,
+                self.height
+            );
+        }
+
+        let (diff, mismatched) = self.diff(expected.pixels(), &actual);
+
+        if mismatched == 0 {
+            return;
+        }
+
+        let actual_path = sibling(path, "actual");
+        let diff_path = sibling(path, "diff");
+
+        // Best effort, the assertion below is what matters.
+        let _ = codec::save(&actual_path, self.width, self.height, &actual);
+        let _ = codec::save(&diff_path, self.width, self.height, &diff);
+
+        panic!(
+            "Snapshot {path:?} differs in {mismatched} pixels (tolerance {}), \
+             see {actual_path:?} and {diff_path:?}",
+            self.tolerance
+        );
+    }
+
+    fn diff(&self, expected: &[u32], actual: &[u32]) -> (Vec<u32>, usize) {
+        let mut mismatched = 0;
+        let diff = expected
+            .iter()
+            .zip(actual)
+            .map(|(&exp, &act)| {
+                if channels_match(exp, act, self.tolerance) {
+                    dim(act)
+                } else {
+                    mismatched += 1;
+                    DIFF_COLOR
+                }
+            })
+            .collect();
+
+        (diff, mismatched)
+    }
+}
+
+const DIFF_COLOR: u32 = 0xff_ff_00_00;
+
+fn channels_match(a: u32, b: u32, tolerance: u8) -> bool {
+    let [_, ar, ag, ab] = a.to_be_bytes();
+    let [_, br, bg, bb] = b.to_be_bytes();
+
+    ar.abs_diff(br) <= tolerance && ag.abs_diff(bg) <= tolerance && ab.abs_diff(bb) <= tolerance
+}
+
+fn dim(color: u32) -> u32 {
+    let [_, r, g, b] = color.to_be_bytes();
+    let luma = (u32::from(r) * 77 + u32::from(g) * 150 + u32::from(b) * 29) >> 10;
+
+    0xff_00_00_00 | luma << 16 | luma << 8 | luma
+}
+
+fn bless_requested() -> bool {
+    env::var_os(BLESS_VAR).is_some_and(|val| !val.is_empty() && val != "0")
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+
+    path.with_file_name(format!("{stem}.{suffix}.ppm"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::panic::{self, AssertUnwindSafe};
+
+    use super::*;
+    use crate::{DrawHandle, Event};
+
+    struct Scene {
+        color: u32,
+    }
+
+    impl MainLoop for Scene {
+        fn handle_event(&mut self, _fb: &mut Framebuffer, _event: &Event) {}
+
+        fn update(&mut self, _fb: &mut Framebuffer, _dt: f32, _time: f64) {}
+
+        fn render(&mut self, d: &mut DrawHandle) {
+            d.clear();
+            d.fill_rect(2, 2, 12, 6, self.color);
+            d.fill_triangle((16., 2.), (30., 2.), (23., 14.), 0xff_00_80_ff);
+            d.draw_line(0, 15, 31, 0, 0xff_ff_ff_ff);
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("picofb-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn panics(f: impl FnOnce()) -> bool {
+        panic::catch_unwind(AssertUnwindSafe(f)).is_err()
+    }
+
+    #[test]
+    fn matches_reference() {
+        let mut scene = Scene {
+            color: 0xff_20_c0_40,
+        };
+
+        Snapshot::new(32, 16, 2).assert(&mut scene, "tests/snapshots/scene.ppm");
+    }
+
+    #[test]
+    fn mismatch_writes_actual_and_diff() {
+        let dir = temp_dir("mismatch");
+        let path = dir.join("scene.ppm");
+        let snapshot = Snapshot::new(32, 16, 1);
+
+        snapshot.check(
+            &mut Scene {
+                color: 0xff_20_c0_40,
+            },
+            &path,
+            true,
+        );
+
+        assert!(panics(|| {
+            snapshot.check(
+                &mut Scene {
+                    color: 0xff_c0_20_40,
+                },
+                &path,
+                false,
+            );
+        }));
+
+        let actual = Image::load(dir.join("scene.actual.ppm")).unwrap();
+        let diff = Image::load(dir.join("scene.diff.ppm")).unwrap();
+
+        assert_eq!(actual.get(2, 2), Some(0xff_c0_20_40));
+        assert_eq!(diff.get(2, 2), Some(DIFF_COLOR));
+        assert_ne!(diff.get(0, 0), Some(DIFF_COLOR));
+        assert_eq!(
+            diff.pixels().iter().filter(|&&p| p == DIFF_COLOR).count(),
+            12 * 6
+        );
+    }
+
+    #[test]
+    fn tolerance_is_per_channel() {
+        let dir = temp_dir("tolerance");
+        let path = dir.join("scene.ppm");
+        let mut snapshot = Snapshot::new(32, 16, 1);
+
+        snapshot.check(
+            &mut Scene {
+                color: 0xff_20_c0_40,
+            },
+            &path,
+            true,
+        );
+
+        snapshot.tolerance = 2;
+        snapshot.check(
+            &mut Scene {
+                color: 0xff_22_be_42,
+            },
+            &path,
+            false,
+        );
+
+        snapshot.tolerance = 1;
+        assert!(panics(|| {
+            snapshot.check(
+                &mut Scene {
+                    color: 0xff_22_be_42,
+                },
+                &path,
+                false,
+            );
+        }));
+    }
+
+    #[test]
+    fn missing_reference_panics() {
+        let dir = temp_dir("missing");
+
+        assert!(panics(|| {
+            Snapshot::new(32, 16, 1).check(&mut Scene { color: 0 }, &dir.join("none.ppm"), false);
+        }));
+    }
+}